@@ -1,8 +1,6 @@
 use mysql_common::prelude::FromRow;
 use rmcp::schemars;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct SearchRequest {
@@ -25,13 +23,3 @@ pub struct TidbSearchHit {
     #[schemars(description = "The content of the tidb server")]
     pub content: String,
 }
-
-#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
-pub struct QdrantSearchHit {
-    #[schemars(description = "The score of the point")]
-    pub score: f64,
-    #[schemars(description = "The payload of the point")]
-    pub payload: HashMap<String, Value>,
-    #[schemars(description = "The vector of the point")]
-    pub vector: Vec<f64>,
-}
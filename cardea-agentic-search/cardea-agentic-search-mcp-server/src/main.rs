@@ -1,6 +1,7 @@
 mod search;
 
 use anyhow::{anyhow, bail};
+use cardea_qdrant_mcp_common::Fusion;
 use clap::{Parser, Subcommand, ValueEnum};
 use mysql::*;
 use regex::Regex;
@@ -44,6 +45,14 @@ enum SearchMode {
         /// The name of the field in the payload that contains the source of the document
         #[arg(long, required = true)]
         qdrant_payload_field: String,
+        /// Names of the vectors to search in Qdrant, separated by commas. Leave empty if the
+        /// collection has a single unnamed vector. If more than one name is given, the results
+        /// of the vectors are fused.
+        #[arg(long, value_delimiter = ',')]
+        qdrant_vector_names: Vec<String>,
+        /// How to fuse the results of the vectors if more than one vector name is given
+        #[arg(long, value_enum, default_value = "rrf")]
+        qdrant_fusion: FusionType,
         /// Maximum number of results to return
         #[arg(long, default_value = "10")]
         limit: u64,
@@ -82,6 +91,14 @@ enum SearchMode {
         /// The name of the field in the payload that contains the source of the document
         #[arg(long, required = true)]
         qdrant_payload_field: String,
+        /// Names of the vectors to search in Qdrant, separated by commas. Leave empty if the
+        /// collection has a single unnamed vector. If more than one name is given, the results
+        /// of the vectors are fused.
+        #[arg(long, value_delimiter = ',')]
+        qdrant_vector_names: Vec<String>,
+        /// How to fuse the results of the vectors if more than one vector name is given
+        #[arg(long, value_enum, default_value = "rrf")]
+        qdrant_fusion: FusionType,
         /// Path to the SSL CA certificate. On macOS, this is typically
         /// `/etc/ssl/cert.pem`. On Debian/Ubuntu/Arch Linux, it's typically
         /// `/etc/ssl/certs/ca-certificates.crt`.
@@ -111,6 +128,22 @@ enum TransportType {
     StreamHttp,
 }
 
+#[derive(Debug, Clone, ValueEnum)]
+enum FusionType {
+    /// Reciprocal Rank Fusion
+    Rrf,
+    /// Distribution-Based Score Fusion
+    Dbsf,
+}
+impl From<FusionType> for Fusion {
+    fn from(value: FusionType) -> Self {
+        match value {
+            FusionType::Rrf => Fusion::Rrf,
+            FusionType::Dbsf => Fusion::Dbsf,
+        }
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::registry()
@@ -127,6 +160,8 @@ async fn main() -> anyhow::Result<()> {
         SearchMode::Qdrant {
             qdrant_collection,
             qdrant_payload_field,
+            qdrant_vector_names,
+            qdrant_fusion,
             limit,
             score_threshold,
             embedding_service,
@@ -149,6 +184,8 @@ async fn main() -> anyhow::Result<()> {
                     base_url: qdrant_base_url,
                     collection: qdrant_collection,
                     payload_source: qdrant_payload_field,
+                    vector_names: qdrant_vector_names,
+                    fusion: qdrant_fusion.into(),
                 }),
                 tidb_config: None,
                 limit,
@@ -238,6 +275,8 @@ async fn main() -> anyhow::Result<()> {
         SearchMode::Search {
             qdrant_collection,
             qdrant_payload_field,
+            qdrant_vector_names,
+            qdrant_fusion,
             tidb_ssl_ca,
             tidb_table_name,
             limit,
@@ -313,6 +352,8 @@ async fn main() -> anyhow::Result<()> {
                     base_url: qdrant_base_url,
                     collection: qdrant_collection,
                     payload_source: qdrant_payload_field,
+                    vector_names: qdrant_vector_names,
+                    fusion: qdrant_fusion.into(),
                 }),
                 tidb_config: Some(TiDBConfig {
                     database: tidb_database,
@@ -384,6 +425,10 @@ pub struct QdrantConfig {
     pub base_url: String,
     pub collection: String,
     pub payload_source: String,
+    /// names of the vectors to search. Empty for collections with a single unnamed vector
+    pub vector_names: Vec<String>,
+    /// how to fuse the results if more than one vector is searched
+    pub fusion: Fusion,
 }
 
 #[derive(Debug, Clone)]
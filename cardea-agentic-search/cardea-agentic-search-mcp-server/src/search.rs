@@ -1,5 +1,6 @@
 use crate::AgenticSearchConfig;
-use cardea_agentic_search_mcp_common::{QdrantSearchHit, SearchRequest, TidbSearchHit};
+use cardea_agentic_search_mcp_common::{SearchRequest, TidbSearchHit};
+use cardea_qdrant_mcp_common::{QdrantError, QdrantResponse, QueryResult, ScoredPoint};
 use endpoints::{
     chat::{
         ChatCompletionObject, ChatCompletionRequestBuilder, ChatCompletionRequestMessage,
@@ -120,8 +121,18 @@ impl AgenticSearchServer {
             );
             let mut output = Vec::new();
             for hit in hits {
-                let source = hit.payload.get(payload_source).unwrap().as_str().unwrap();
-                output.push(source.to_string());
+                match hit
+                    .payload
+                    .as_ref()
+                    .and_then(|payload| payload.get(payload_source))
+                    .and_then(Value::as_str)
+                {
+                    Some(source) => output.push(source.to_string()),
+                    None => warn!(
+                        "The payload of point {:?} has no string field `{}`",
+                        hit.id, payload_source
+                    ),
+                }
             }
 
             info!("Vector search done! 🎉");
@@ -269,23 +280,66 @@ impl AgenticSearchServer {
     async fn search_in_qdrant(
         &self,
         vector: impl AsRef<[f64]>,
-    ) -> Result<Vec<QdrantSearchHit>, McpError> {
+    ) -> Result<Vec<ScoredPoint>, McpError> {
         match &self.config.qdrant_config {
             Some(qdrant_config) => {
                 let base_url = qdrant_config.base_url.trim_end_matches('/');
-                let url = format!(
-                    "{}/collections/{}/points/search",
-                    base_url, qdrant_config.collection
-                );
-
-                // build params
-                let params = json!({
-                    "vector": vector.as_ref().to_vec(),
-                    "limit": self.config.limit,
-                    "with_payload": true,
-                    "with_vector": true,
-                    "score_threshold": self.config.score_threshold,
-                });
+                let vector = vector.as_ref().to_vec();
+
+                // build url and params. A single vector is searched with the search API, while
+                // multiple named vectors are searched with the query API and their results fused.
+                let (url, params) = match qdrant_config.vector_names.as_slice() {
+                    [] => (
+                        format!(
+                            "{}/collections/{}/points/search",
+                            base_url, qdrant_config.collection
+                        ),
+                        json!({
+                            "vector": vector,
+                            "limit": self.config.limit,
+                            "with_payload": true,
+                            "score_threshold": self.config.score_threshold,
+                        }),
+                    ),
+                    [name] => (
+                        format!(
+                            "{}/collections/{}/points/search",
+                            base_url, qdrant_config.collection
+                        ),
+                        json!({
+                            "vector": { "name": name, "vector": vector },
+                            "limit": self.config.limit,
+                            "with_payload": true,
+                            "score_threshold": self.config.score_threshold,
+                        }),
+                    ),
+                    names => {
+                        let prefetch = names
+                            .iter()
+                            .map(|name| {
+                                json!({
+                                    "query": vector,
+                                    "using": name,
+                                    "limit": self.config.limit,
+                                    "score_threshold": self.config.score_threshold,
+                                })
+                            })
+                            .collect::<Vec<_>>();
+
+                        (
+                            format!(
+                                "{}/collections/{}/points/query",
+                                base_url, qdrant_config.collection
+                            ),
+                            json!({
+                                "prefetch": prefetch,
+                                "query": { "fusion": qdrant_config.fusion },
+                                "limit": self.config.limit,
+                                "with_payload": true,
+                            }),
+                        )
+                    }
+                };
 
                 let response = match &qdrant_config.api_key {
                     Some(api_key) => reqwest::Client::new()
@@ -314,82 +368,36 @@ impl AgenticSearchServer {
                 };
 
                 let status = response.status();
-                if !status.is_success() {
-                    let error_message =
-                        format!("Failed to send search request to Qdrant server. Status: {status}");
-                    error!("{}", error_message);
-                    return Err(McpError::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        error_message,
-                        None,
-                    ));
-                }
+                let body = response.bytes().await.map_err(|e| {
+                    let err_msg = format!("Failed to search points: {e}");
+                    error!("{}", err_msg);
+                    McpError::new(ErrorCode::INTERNAL_ERROR, err_msg, None)
+                })?;
 
-                match response.json::<Value>().await {
-                    Ok(json) => match json.get("result") {
-                        Some(result) => {
-                            let hits = result
-                                .as_array()
-                                .unwrap()
-                                .iter()
-                                .map(|v| QdrantSearchHit {
-                                    score: v.get("score").unwrap().as_f64().unwrap(),
-                                    payload: v
-                                        .get("payload")
-                                        .unwrap()
-                                        .as_object()
-                                        .unwrap()
-                                        .to_owned()
-                                        .into_iter()
-                                        .map(|(k, v)| (k.to_string(), v.clone()))
-                                        .collect(),
-                                    vector: v
-                                        .get("vector")
-                                        .unwrap()
-                                        .as_array()
-                                        .unwrap()
-                                        .to_owned()
-                                        .iter()
-                                        .map(|v| v.as_f64().unwrap())
-                                        .collect::<Vec<f64>>(),
-                                })
-                                .collect();
-
-                            Ok(hits)
-                        }
-                        None => {
-                            debug!(
-                                "Qdrant search response:\n{}",
-                                serde_json::to_string_pretty(&json).unwrap()
-                            );
-
-                            match json.get("status") {
-                                Some(status) => {
-                                    let error_message = format!(
-                                        "Failed to search points. {}",
-                                        status.get("error").unwrap().as_str().unwrap()
-                                    );
-                                    error!("{}", error_message);
-                                    Err(McpError::new(
-                                        ErrorCode::INTERNAL_ERROR,
-                                        error_message,
-                                        None,
-                                    ))
-                                }
-                                None => {
-                                    let error_message = "Failed to search points. ";
-                                    error!("{}", error_message);
-                                    Err(McpError::new(
-                                        ErrorCode::INTERNAL_ERROR,
-                                        error_message,
-                                        None,
-                                    ))
-                                }
+                // the query API wraps the scored points in `points`
+                let parsed = match qdrant_config.vector_names.len() > 1 {
+                    true => QdrantResponse::<QueryResult>::parse(&body)
+                        .map(|(result, time)| (result.points, time)),
+                    false => QdrantResponse::<Vec<ScoredPoint>>::parse(&body),
+                };
+
+                match parsed {
+                    Ok((hits, time)) if status.is_success() => {
+                        debug!("Qdrant search took {} seconds", time);
+                        Ok(hits)
+                    }
+                    result => {
+                        let error_message = match result {
+                            Err(QdrantError::Status(error)) => {
+                                format!("Failed to search points: {status}. {error}")
+                            }
+                            Err(e) if status.is_success() => {
+                                format!("Failed to search points. {e}")
                             }
-                        }
-                    },
-                    Err(e) => {
-                        let error_message = format!("Failed to search points: {e}");
+                            _ => format!(
+                                "Failed to send search request to Qdrant server. Status: {status}"
+                            ),
+                        };
                         error!("{}", error_message);
                         Err(McpError::new(
                             ErrorCode::INTERNAL_ERROR,
@@ -1,5 +1,7 @@
 use rmcp::schemars;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::HashMap, fmt};
 
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListIndicesResponse {
@@ -73,10 +75,491 @@ pub struct AliasInfo {
     pub is_write_index: String,
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetMappingRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetMappingResponse {
+    pub indices: Vec<IndexMapping>,
+}
+impl From<rmcp::model::CallToolResult> for GetMappingResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<GetMappingResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IndexMapping {
+    /// index name
+    pub index: String,
+    /// the mapped fields, including the sub-fields of objects and the multi-fields, e.g. `title.keyword`
+    pub fields: Vec<FieldMapping>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FieldMapping {
+    /// the full path of the field
+    pub name: String,
+    /// the field type, e.g. `text`, `keyword`, `date`, or `object` for an object without a type
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// the analyzer of a text field, if not the default one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub analyzer: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetSettingsRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetSettingsResponse {
+    pub indices: Vec<IndexSettings>,
+}
+impl From<rmcp::model::CallToolResult> for GetSettingsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<GetSettingsResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IndexSettings {
+    /// index name
+    pub index: String,
+    /// the settings of the index in flat format, e.g. `index.number_of_shards`
+    pub settings: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct FieldCapsRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(
+        description = "The names of the fields to get the capabilities of. Wildcards are supported, e.g. `user.*`. Defaults to all the fields except the metadata fields"
+    )]
+    pub fields: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FieldCapsResponse {
+    pub fields: Vec<FieldCapability>,
+}
+impl From<rmcp::model::CallToolResult> for FieldCapsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<FieldCapsResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct FieldCapability {
+    /// the full path of the field
+    pub name: String,
+    /// the field type
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// whether the field can be searched
+    pub searchable: bool,
+    /// whether the field can be aggregated or sorted on
+    pub aggregatable: bool,
+    /// the indices that have the field with this type, if the type differs across indices
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indices: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ClusterHealthResponse {
+    pub cluster_name: String,
+    /// `green`, `yellow` or `red`
+    pub status: String,
+    pub timed_out: bool,
+    pub number_of_nodes: u64,
+    pub number_of_data_nodes: u64,
+    pub active_primary_shards: u64,
+    pub active_shards: u64,
+    pub relocating_shards: u64,
+    pub initializing_shards: u64,
+    pub unassigned_shards: u64,
+    pub delayed_unassigned_shards: u64,
+    pub number_of_pending_tasks: u64,
+    pub active_shards_percent_as_number: f64,
+}
+impl From<rmcp::model::CallToolResult> for ClusterHealthResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ClusterHealthResponse>(content).unwrap()
+    }
+}
+
+/// summary of the cluster statistics
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ClusterStatsResponse {
+    pub cluster_name: String,
+    /// `green`, `yellow` or `red`
+    pub status: String,
+    /// number of nodes by role, e.g. `total`, `data`, `master`
+    pub nodes: HashMap<String, u64>,
+    /// Elasticsearch versions of the nodes
+    pub versions: Vec<String>,
+    pub indices_count: u64,
+    pub shards_total: u64,
+    pub shards_primaries: u64,
+    pub docs_count: u64,
+    pub docs_deleted: u64,
+    pub store_size_in_bytes: u64,
+    pub heap_used_in_bytes: u64,
+    pub heap_max_in_bytes: u64,
+    pub disk_total_in_bytes: u64,
+    pub disk_available_in_bytes: u64,
+}
+impl From<rmcp::model::CallToolResult> for ClusterStatsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ClusterStatsResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NodesStatsResponse {
+    pub nodes: Vec<NodeStats>,
+}
+impl From<rmcp::model::CallToolResult> for NodesStatsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<NodesStatsResponse>(content).unwrap()
+    }
+}
+
+/// summary of the statistics of a node
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct NodeStats {
+    pub name: String,
+    pub host: Option<String>,
+    /// roles of the node, e.g. `master`, `data`, `ingest`
+    pub roles: Vec<String>,
+    pub cpu_percent: Option<u64>,
+    pub load_average_1m: Option<f64>,
+    pub heap_used_percent: Option<u64>,
+    pub disk_total_in_bytes: Option<u64>,
+    pub disk_available_in_bytes: Option<u64>,
+    pub docs_count: Option<u64>,
+    pub store_size_in_bytes: Option<u64>,
+    /// number of search queries since the node started
+    pub search_query_total: Option<u64>,
+    pub search_query_time_in_millis: Option<u64>,
+    /// number of indexing operations since the node started
+    pub indexing_index_total: Option<u64>,
+    pub indexing_index_time_in_millis: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct IndexStatsRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IndexStatsResponse {
+    pub indices: Vec<IndexStats>,
+}
+impl From<rmcp::model::CallToolResult> for IndexStatsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<IndexStatsResponse>(content).unwrap()
+    }
+}
+
+/// summary of the statistics of an index
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IndexStats {
+    /// index name
+    pub index: String,
+    pub docs_count: u64,
+    pub docs_deleted: u64,
+    /// size of the primary shards
+    pub primaries_store_size_in_bytes: u64,
+    /// size of the primary and replica shards
+    pub total_store_size_in_bytes: u64,
+    /// number of indexing operations on the primary shards
+    pub indexing_index_total: u64,
+    pub indexing_index_time_in_millis: u64,
+    /// number of search queries on the primary and replica shards
+    pub search_query_total: u64,
+    pub search_query_time_in_millis: u64,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CatShardsRequest {
+    #[schemars(
+        description = "The name or pattern of the indices, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to all the allowed indices"
+    )]
+    pub index: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CatShardsResponse {
+    pub shards: Vec<ShardInfo>,
+}
+impl From<rmcp::model::CallToolResult> for CatShardsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<CatShardsResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ShardInfo {
+    /// index name
+    pub index: String,
+    /// shard number
+    pub shard: String,
+    /// `p` for a primary shard, `r` for a replica shard
+    pub prirep: String,
+    /// `STARTED`, `RELOCATING`, `INITIALIZING` or `UNASSIGNED`
+    pub state: String,
+    /// number of documents
+    pub docs: Option<String>,
+    /// store size
+    pub store: Option<String>,
+    /// node name
+    pub node: Option<String>,
+    /// reason why the shard is unassigned
+    #[serde(rename = "unassigned.reason")]
+    pub unassigned_reason: Option<String>,
+    /// why the shard can not be allocated, for the unassigned shards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<AllocationExplanation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AllocationExplanation {
+    /// e.g. `no`, `yes`, `throttled`, `awaiting_info`
+    pub can_allocate: Option<String>,
+    pub allocate_explanation: Option<String>,
+    /// the reasons why the nodes refuse the shard, e.g. `disk_threshold: the node is above the high watermark`
+    pub decider_explanations: Vec<String>,
+}
+
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct SearchRequest {
     /// user query
     pub query: String,
+    #[schemars(
+        description = "The name of the index, alias or index pattern to search, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(
+        description = "The fields to search, with optional boosts, e.g. `[\"title^3\", \"content\"]`. Defaults to the fields of the server"
+    )]
+    pub fields: Option<Vec<String>>,
+    #[schemars(description = "The number of hits to return")]
+    pub size: Option<u64>,
+    #[schemars(description = "The number of hits to skip, to get the next pages")]
+    pub from: Option<u64>,
+    #[schemars(
+        description = "How to interpret the query. `multi_match` (default) matches the words of the query; `query_string` supports the Lucene syntax, e.g. `title:\"new york\" AND (hotel OR inn) -cheap`, `price:[10 TO 100]`, `colour~1`; `simple_query_string` supports a forgiving syntax, e.g. `\"new york\" +hotel -cheap`, that never fails on invalid input"
+    )]
+    pub mode: Option<QueryMode>,
+    #[schemars(
+        description = "Whether to return highlighted fragments of the fields that match the query. Always enabled for the `text` format"
+    )]
+    pub highlight: Option<bool>,
+    #[schemars(
+        description = "The size of the highlighted fragments in characters. Defaults to 150"
+    )]
+    pub fragment_size: Option<u64>,
+    #[schemars(
+        description = "The maximum number of highlighted fragments per field. Defaults to 3"
+    )]
+    pub number_of_fragments: Option<u64>,
+    #[schemars(
+        description = "The fields of the source to return, e.g. `[\"title\", \"author.*\"]`. Defaults to all the fields"
+    )]
+    pub source_includes: Option<Vec<String>>,
+    #[schemars(description = "The fields of the source not to return, e.g. `[\"content\"]`")]
+    pub source_excludes: Option<Vec<String>>,
+    #[schemars(
+        description = "`json` (default) returns the hits with their source; `text` returns only the title and the best fragments of each hit, which is much shorter for long documents"
+    )]
+    pub format: Option<ResultFormat>,
+    #[schemars(
+        description = "Whether to profile the search. The time spent by each shard in the query, the rewrite, the collectors and the aggregations is returned as a second content, e.g. to compare the cost of field boosts"
+    )]
+    pub profile: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultFormat {
+    #[default]
+    Json,
+    Text,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMode {
+    #[default]
+    MultiMatch,
+    QueryString,
+    SimpleQueryString,
+}
+
+/// summary of the profile of a search
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SearchProfile {
+    /// shards, the slowest first
+    pub shards: Vec<ShardProfile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ShardProfile {
+    /// id of the shard, e.g. `[node id][index][0]`
+    pub id: String,
+    /// time spent in the queries, in milliseconds
+    pub query_ms: f64,
+    /// time spent rewriting the queries, in milliseconds
+    pub rewrite_ms: f64,
+    /// time spent collecting the hits, in milliseconds
+    pub collector_ms: f64,
+    /// time spent in the aggregations, in milliseconds
+    pub aggregation_ms: f64,
+    /// time spent fetching the hits, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetch_ms: Option<f64>,
+    /// Lucene queries that the search was rewritten into
+    pub queries: Vec<QueryProfile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct QueryProfile {
+    /// Lucene query type, e.g. `BooleanQuery` or `TermQuery`
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// Lucene query, e.g. `title:paris^3.0`
+    pub description: String,
+    pub time_ms: f64,
+    /// phases that took the most time, e.g. `score` or `build_scorer`, in milliseconds
+    pub top_phases: HashMap<String, f64>,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub children: Vec<QueryProfile>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ExplainDocumentRequest {
+    #[schemars(
+        description = "The name of the index of the document, e.g. the `_index` of a hit. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(description = "The id of the document")]
+    pub id: String,
+    #[schemars(
+        description = "The query of the `search` tool to explain. Either `query` or `query_dsl` must be given"
+    )]
+    pub query: Option<String>,
+    #[schemars(
+        description = "The fields searched by `query`, with optional boosts, e.g. `[\"title^3\", \"content\"]`. Defaults to the fields of the server"
+    )]
+    pub fields: Option<Vec<String>>,
+    #[schemars(
+        description = "How to interpret `query`, as in the `search` tool: `multi_match` (default), `query_string` or `simple_query_string`"
+    )]
+    pub mode: Option<QueryMode>,
+    #[schemars(
+        description = "A query clause in the Elasticsearch query DSL to explain instead of `query`, e.g. `{\"match\": {\"title\": \"paris\"}}`"
+    )]
+    pub query_dsl: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExplainDocumentResponse {
+    pub index: String,
+    pub id: String,
+    /// whether the document matches the query
+    pub matched: bool,
+    /// score of the document. `null` if the document does not match
+    pub score: Option<f64>,
+    /// how the score is computed, one line per step, indented by the depth of the step
+    pub explanation: String,
+}
+impl From<rmcp::model::CallToolResult> for ExplainDocumentResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ExplainDocumentResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SemanticSearchRequest {
+    #[schemars(description = "The query to search for by meaning")]
+    pub query: String,
+    #[schemars(
+        description = "`knn` (default) finds the documents whose embedding is nearest to the embedding of the query; `hybrid` fuses the kNN results with the results of a keyword search on the configured fields using reciprocal rank fusion"
+    )]
+    pub mode: Option<SemanticMode>,
+    #[schemars(
+        description = "The query clause in the Elasticsearch query DSL that filters the documents before the search, e.g. `{\"term\": {\"lang\": \"en\"}}`"
+    )]
+    pub filter: Option<Value>,
+    #[schemars(description = "The number of hits to return")]
+    pub size: Option<u64>,
+    #[schemars(
+        description = "The number of nearest neighbour candidates to consider per shard. Higher values are more accurate and slower"
+    )]
+    pub num_candidates: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SemanticMode {
+    #[default]
+    Knn,
+    Hybrid,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DslSearchRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern to search, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(
+        description = "The query clause in the Elasticsearch query DSL, e.g. `{\"bool\": {\"must\": [{\"match_phrase\": {\"title\": \"new york\"}}], \"filter\": [{\"range\": {\"price\": {\"lte\": 100}}}]}}` or `{\"fuzzy\": {\"title\": {\"value\": \"colour\"}}}`. Defaults to matching all documents"
+    )]
+    pub query: Option<Value>,
+    #[schemars(
+        description = "The sort order, e.g. `[{\"date\": \"desc\"}, \"_score\"]`. Required by `search_after`"
+    )]
+    pub sort: Option<Value>,
+    #[serde(rename = "_source")]
+    #[schemars(
+        description = "The fields of the source to return: `false` to return no source, a list of field patterns, or an object with `includes` and `excludes` lists"
+    )]
+    pub source: Option<Value>,
+    #[schemars(description = "The number of hits to skip. Must not be used with `search_after`")]
+    pub from: Option<u64>,
+    #[schemars(description = "The number of hits to return")]
+    pub size: Option<u64>,
+    #[schemars(
+        description = "The `sort` values of the last hit of the previous page, to get the next page"
+    )]
+    pub search_after: Option<Vec<Value>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
@@ -96,19 +579,533 @@ impl From<rmcp::model::CallToolResult> for SearchResponse {
     }
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AggregateRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern to aggregate, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(
+        description = "The query clause in the Elasticsearch query DSL that filters the documents to aggregate, e.g. `{\"range\": {\"@timestamp\": {\"gte\": \"now-7d/d\"}}}`. Defaults to all documents"
+    )]
+    pub query: Option<Value>,
+    #[schemars(
+        description = "The aggregations to compute. Each aggregation is returned as a table of rows"
+    )]
+    pub aggregations: Vec<Aggregation>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct Aggregation {
+    #[schemars(
+        description = "The name of the aggregation, used as the column name of its keys or values, e.g. `service`"
+    )]
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: AggregationKind,
+    #[serde(default)]
+    #[schemars(
+        description = "The aggregations to compute for each bucket. Only allowed for `terms`, `date_histogram` and `histogram` aggregations"
+    )]
+    pub sub_aggregations: Vec<Aggregation>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AggregationKind {
+    #[schemars(description = "A bucket for each unique value of a field")]
+    Terms {
+        #[schemars(description = "The name of the field, e.g. `service.keyword`")]
+        field: String,
+        #[schemars(
+            description = "The number of buckets to return, the most frequent values first. Defaults to 10"
+        )]
+        size: Option<u64>,
+    },
+    #[schemars(description = "A bucket for each interval of a date field")]
+    DateHistogram {
+        #[schemars(description = "The name of the date field, e.g. `@timestamp`")]
+        field: String,
+        #[schemars(
+            description = "A calendar interval, e.g. `day`, `week`, `month`. Either this or `fixed_interval` is required"
+        )]
+        calendar_interval: Option<String>,
+        #[schemars(
+            description = "A fixed interval, e.g. `12h`, `30m`. Either this or `calendar_interval` is required"
+        )]
+        fixed_interval: Option<String>,
+        #[schemars(description = "The format of the keys, e.g. `yyyy-MM-dd`")]
+        format: Option<String>,
+        #[schemars(
+            description = "The time zone of the intervals, e.g. `+01:00` or `Europe/Paris`"
+        )]
+        time_zone: Option<String>,
+    },
+    #[schemars(description = "A bucket for each interval of a numeric field")]
+    Histogram {
+        #[schemars(description = "The name of the numeric field")]
+        field: String,
+        #[schemars(description = "The size of the intervals")]
+        interval: f64,
+    },
+    #[schemars(description = "The count, min, max, avg and sum of a numeric field")]
+    Stats {
+        #[schemars(description = "The name of the numeric field")]
+        field: String,
+    },
+    #[schemars(description = "The approximate number of unique values of a field")]
+    Cardinality {
+        #[schemars(description = "The name of the field")]
+        field: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AggregateResponse {
+    /// The number of milliseconds it took Elasticsearch to run the request.
+    pub took: u64,
+    /// number of documents that were aggregated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<TotalHits>,
+    pub aggregations: Vec<AggregationTable>,
+}
+impl From<rmcp::model::CallToolResult> for AggregateResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<AggregateResponse>(content).unwrap()
+    }
+}
+
+/// flattened result of a top-level aggregation. Each row holds, for each bucket aggregation
+/// on the path to a bucket, the key (`<name>`) and the document count (`<name>.doc_count`) of the
+/// bucket, and the values of the metric aggregations (`<name>` or `<name>.<stat>`).
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AggregationTable {
+    pub name: String,
+    pub rows: Vec<serde_json::Map<String, Value>>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetDocumentRequest {
+    #[schemars(
+        description = "The name of the index or alias. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(description = "The id of the document")]
+    pub id: String,
+    #[schemars(
+        description = "The fields of the source to return, e.g. `[\"title\", \"author.*\"]`. Defaults to all the fields"
+    )]
+    pub source_includes: Option<Vec<String>>,
+    #[schemars(description = "The fields of the source not to return, e.g. `[\"content\"]`")]
+    pub source_excludes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct MgetRequest {
+    #[schemars(
+        description = "The name of the index or alias. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(description = "The ids of the documents, at most 100")]
+    pub ids: Vec<String>,
+    #[schemars(
+        description = "The fields of the source to return, e.g. `[\"title\", \"author.*\"]`. Defaults to all the fields"
+    )]
+    pub source_includes: Option<Vec<String>>,
+    #[schemars(description = "The fields of the source not to return, e.g. `[\"content\"]`")]
+    pub source_excludes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct MgetResponse {
+    /// documents, in the order of the ids
+    pub docs: Vec<Document>,
+}
+impl From<rmcp::model::CallToolResult> for MgetResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<MgetResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Document {
+    #[serde(rename = "_index")]
+    pub index: String,
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// whether the document exists
+    #[serde(default)]
+    pub found: bool,
+    #[serde(rename = "_version", skip_serializing_if = "Option::is_none")]
+    pub version: Option<u64>,
+    #[serde(rename = "_source", skip_serializing_if = "Option::is_none")]
+    pub source: Option<Value>,
+}
+impl From<rmcp::model::CallToolResult> for Document {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<Document>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CountRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern to count in, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(
+        description = "The query clause in the Elasticsearch query DSL that matches the documents to count, e.g. `{\"match\": {\"title\": \"paris\"}}`. Defaults to all documents"
+    )]
+    pub query: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CountResponse {
+    /// number of matching documents
+    pub count: u64,
+}
+impl From<rmcp::model::CallToolResult> for CountResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<CountResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreateIndexRequest {
+    #[schemars(description = "The name of the index to create")]
+    pub index: String,
+    #[schemars(
+        description = "The mappings of the index, e.g. `{\"properties\": {\"title\": {\"type\": \"text\"}, \"created\": {\"type\": \"date\"}}}`"
+    )]
+    pub mappings: Option<Value>,
+    #[schemars(
+        description = "The settings of the index, e.g. `{\"number_of_shards\": 1, \"number_of_replicas\": 1}`"
+    )]
+    pub settings: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CreateIndexResponse {
+    pub acknowledged: bool,
+    pub shards_acknowledged: bool,
+    pub index: String,
+}
+impl From<rmcp::model::CallToolResult> for CreateIndexResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<CreateIndexResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct IndexDocumentRequest {
+    #[schemars(
+        description = "The id of the document. If a document with this id exists, it is replaced. Defaults to a generated id"
+    )]
+    pub id: Option<String>,
+    #[schemars(description = "The document to index")]
+    pub document: Value,
+    #[schemars(
+        description = "Whether to wait until the document is visible to searches before returning"
+    )]
+    pub refresh: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct UpdateDocumentRequest {
+    #[schemars(description = "The id of the document to update")]
+    pub id: String,
+    #[schemars(
+        description = "The fields to update. The fields that are not given are left unchanged"
+    )]
+    pub doc: Value,
+    #[schemars(description = "Whether to create the document if it does not exist")]
+    pub doc_as_upsert: Option<bool>,
+    #[schemars(
+        description = "Whether to wait until the update is visible to searches before returning"
+    )]
+    pub refresh: Option<bool>,
+}
+
+/// result of a write to a single document
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct WriteDocumentResponse {
+    #[serde(rename = "_index")]
+    pub index: String,
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_version")]
+    pub version: u64,
+    /// `created`, `updated`, `deleted` or `noop`
+    pub result: String,
+}
+impl From<rmcp::model::CallToolResult> for WriteDocumentResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<WriteDocumentResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct BulkIndexRequest {
+    #[schemars(
+        description = "The documents to index in NDJSON format: one JSON document per line, without action lines"
+    )]
+    pub documents: String,
+    #[schemars(
+        description = "The name of the field of the documents whose value is used as the document id. Defaults to generated ids"
+    )]
+    pub id_field: Option<String>,
+    #[schemars(
+        description = "Whether to wait until the documents are visible to searches before returning"
+    )]
+    pub refresh: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct BulkIndexResponse {
+    /// The number of milliseconds it took Elasticsearch to run the request.
+    pub took: u64,
+    /// number of documents that were indexed
+    pub indexed: u64,
+    /// number of documents that failed to be indexed
+    pub failed: u64,
+    /// result of each document, in the order of the lines
+    pub items: Vec<BulkItemResult>,
+}
+impl From<rmcp::model::CallToolResult> for BulkIndexResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<BulkIndexResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct BulkItemResult {
+    /// line of the document, starting from 1
+    pub line: usize,
+    /// id of the document
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// HTTP status of the operation
+    pub status: u16,
+    /// `created` or `updated` if the document was indexed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// reason why the document failed to be indexed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeleteByQueryRequest {
+    #[schemars(
+        description = "The query clause in the Elasticsearch query DSL that matches the documents to delete, e.g. `{\"term\": {\"doc_id\": \"paris-001\"}}`"
+    )]
+    pub query: Value,
+    #[schemars(description = "The maximum number of documents to delete")]
+    pub max_docs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DeleteByQueryResponse {
+    /// The number of milliseconds it took Elasticsearch to run the request.
+    pub took: u64,
+    /// number of documents that matched the query
+    pub total: u64,
+    /// number of documents that were deleted
+    pub deleted: u64,
+    /// number of documents that were changed while being deleted, and were not deleted
+    pub version_conflicts: u64,
+    /// failures of the deletion, if any
+    #[serde(default)]
+    pub failures: Vec<Value>,
+}
+impl From<rmcp::model::CallToolResult> for DeleteByQueryResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<DeleteByQueryResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct PageSearchRequest {
+    #[schemars(
+        description = "The cursor returned by the previous page. If given, the other arguments are ignored and the next page of the same search is returned"
+    )]
+    pub cursor: Option<String>,
+    #[schemars(
+        description = "The name of the index, alias or index pattern to search, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(
+        description = "The query clause in the Elasticsearch query DSL, e.g. `{\"match\": {\"title\": \"paris\"}}`. Defaults to matching all documents"
+    )]
+    pub query: Option<Value>,
+    #[schemars(
+        description = "The sort order, e.g. `[{\"date\": \"desc\"}]`. Defaults to the score"
+    )]
+    pub sort: Option<Value>,
+    #[schemars(
+        description = "The fields of the source to return, e.g. `[\"title\", \"author.*\"]`. Defaults to all the fields"
+    )]
+    pub source_includes: Option<Vec<String>>,
+    #[schemars(description = "The fields of the source not to return, e.g. `[\"content\"]`")]
+    pub source_excludes: Option<Vec<String>>,
+    #[schemars(description = "The number of hits per page")]
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PageSearchResponse {
+    /// number of matching documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<TotalHits>,
+    pub hits: Vec<Hit>,
+    /// cursor to get the next page. `null` if this is the last page
+    pub cursor: Option<String>,
+}
+impl From<rmcp::model::CallToolResult> for PageSearchResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<PageSearchResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ExportRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern to export, e.g. `logs-*`, or a comma-separated list of them. Must be allowed by the server. Defaults to the index of the server"
+    )]
+    pub index: Option<String>,
+    #[schemars(
+        description = "The query clause in the Elasticsearch query DSL that matches the documents to export. Defaults to all documents"
+    )]
+    pub query: Option<Value>,
+    #[schemars(
+        description = "The sort order, e.g. `[{\"date\": \"asc\"}]`. Defaults to the index order, which is the fastest"
+    )]
+    pub sort: Option<Value>,
+    #[schemars(
+        description = "The fields of the source to export, e.g. `[\"title\", \"author.name\"]`. They are the columns of the CSV format. Defaults to all the fields"
+    )]
+    pub fields: Option<Vec<String>>,
+    #[schemars(description = "`ndjson` (default) or `csv`")]
+    pub format: Option<ExportFormat>,
+    #[schemars(
+        description = "The maximum number of documents to export. Defaults to, and can not exceed, the limit of the server"
+    )]
+    pub max_rows: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    #[default]
+    Ndjson,
+    Csv,
+}
+
+/// summary of an export. The exported documents follow as a second text content
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ExportResponse {
+    pub format: ExportFormat,
+    /// number of exported documents
+    pub rows: u64,
+    /// `true` if more documents match than were exported
+    pub truncated: bool,
+}
+impl From<rmcp::model::CallToolResult> for ExportResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ExportResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct EsqlQueryRequest {
+    #[schemars(
+        description = "The ES|QL query, e.g. `FROM logs-* | WHERE level == \"error\" | STATS errors = COUNT(*) BY service | SORT errors DESC`. Must start with `FROM`, `ROW` or `SHOW`"
+    )]
+    pub query: String,
+    #[schemars(
+        description = "The maximum number of rows to return. Defaults to 100, at most 1000"
+    )]
+    pub limit: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SqlQueryRequest {
+    #[schemars(
+        description = "The Elasticsearch SQL query, e.g. `SELECT service, COUNT(*) AS errors FROM \"logs-*\" WHERE level = 'error' GROUP BY service`. Only `SELECT` statements are allowed"
+    )]
+    pub query: String,
+    #[schemars(
+        description = "The maximum number of rows to return. Defaults to 100, at most 1000"
+    )]
+    pub limit: Option<u64>,
+}
+
+/// result of an ES|QL or SQL query
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TabularQueryResponse {
+    pub columns: Vec<Column>,
+    /// rows, with a value per column
+    pub rows: Vec<Vec<Value>>,
+    /// `true` if there are more rows than returned
+    pub truncated: bool,
+}
+impl From<rmcp::model::CallToolResult> for TabularQueryResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<TabularQueryResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Column {
+    pub name: String,
+    /// type of the values, e.g. `keyword`, `long`, `date`
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Hits {
+    /// number of matching documents
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<TotalHits>,
     pub hits: Vec<Hit>,
 }
 
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TotalHits {
+    pub value: u64,
+    /// `eq` if the value is accurate, `gte` if it is a lower bound
+    pub relation: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct Hit {
     #[serde(rename = "_index")]
     pub index: String,
+    #[serde(rename = "_id", default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// score of the hit. `null` if the hits are sorted by a field other than `_score`
     #[serde(rename = "_score")]
-    pub score: f64,
-    #[serde(rename = "_source")]
+    pub score: Option<f64>,
+    #[serde(rename = "_source", default)]
     pub source: serde_json::Value,
+    /// sort values of the hit, used as `search_after` to get the next page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<Vec<Value>>,
+    /// highlighted fragments of each field that matches the query
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<HashMap<String, Vec<String>>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
@@ -138,3 +1135,302 @@ pub struct Reason {
     /// A human-readable explanation of the error, in English.
     pub reason: String,
 }
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ListTemplatesRequest {
+    #[schemars(
+        description = "The name or the pattern of the templates to list, e.g. `logs-*`. Defaults to all the templates"
+    )]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListIndexTemplatesResponse {
+    pub templates: Vec<IndexTemplate>,
+}
+impl From<rmcp::model::CallToolResult> for ListIndexTemplatesResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ListIndexTemplatesResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IndexTemplate {
+    pub name: String,
+    /// patterns of the names of the indices that the template applies to
+    pub index_patterns: Vec<String>,
+    /// component templates that the template is composed of, in order
+    #[serde(default)]
+    pub composed_of: Vec<String>,
+    /// template with the highest priority applies to a new index
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u64>,
+    /// settings, mappings and aliases of the template
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListComponentTemplatesResponse {
+    pub templates: Vec<ComponentTemplate>,
+}
+impl From<rmcp::model::CallToolResult> for ListComponentTemplatesResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ListComponentTemplatesResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ComponentTemplate {
+    pub name: String,
+    /// settings, mappings and aliases of the template
+    pub template: Value,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct PutIndexTemplateRequest {
+    #[schemars(description = "The name of the template. An existing template is replaced")]
+    pub name: String,
+    #[schemars(
+        description = "The patterns of the names of the indices that the template applies to, e.g. `[\"products-*\"]`"
+    )]
+    pub index_patterns: Vec<String>,
+    #[schemars(description = "The component templates that the template is composed of, in order")]
+    pub composed_of: Option<Vec<String>>,
+    #[schemars(
+        description = "The priority of the template. The template with the highest priority applies to a new index"
+    )]
+    pub priority: Option<u64>,
+    #[schemars(
+        description = "The settings, mappings and aliases of the template, e.g. `{\"settings\": {\"number_of_shards\": 1}, \"mappings\": {\"properties\": {\"title\": {\"type\": \"text\"}}}}`"
+    )]
+    pub template: Option<Value>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct PutComponentTemplateRequest {
+    #[schemars(description = "The name of the component template. An existing one is replaced")]
+    pub name: String,
+    #[schemars(
+        description = "The settings, mappings and aliases of the component template, e.g. `{\"mappings\": {\"properties\": {\"@timestamp\": {\"type\": \"date\"}}}}`"
+    )]
+    pub template: Value,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeleteTemplateRequest {
+    #[schemars(description = "The name of the template to delete")]
+    pub name: String,
+}
+
+/// response of Elasticsearch to a change of the cluster metadata
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AcknowledgedResponse {
+    /// whether the change was acknowledged by all the nodes
+    pub acknowledged: bool,
+}
+impl From<rmcp::model::CallToolResult> for AcknowledgedResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<AcknowledgedResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListIlmPoliciesResponse {
+    pub policies: Vec<IlmPolicy>,
+}
+impl From<rmcp::model::CallToolResult> for ListIlmPoliciesResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ListIlmPoliciesResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IlmPolicy {
+    pub name: String,
+    /// phases of the policy, in order, e.g. `hot`, `warm` and `delete`
+    pub phases: Vec<IlmPhase>,
+    /// indices managed by the policy
+    pub indices: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IlmPhase {
+    pub name: String,
+    /// age of the index at which it enters the phase, e.g. `30d`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_age: Option<String>,
+    /// actions of the phase, e.g. `rollover` or `delete`
+    pub actions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct AttachIlmPolicyRequest {
+    #[schemars(
+        description = "The name of the index, or a pattern of indices, to attach the policy to. Must be allowed by the server"
+    )]
+    pub index: String,
+    #[schemars(description = "The name of the ILM policy")]
+    pub policy: String,
+    #[schemars(
+        description = "The alias that is rolled over by the `rollover` action of the policy, if any"
+    )]
+    pub rollover_alias: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct UpdateAliasesRequest {
+    #[schemars(
+        description = "The alias actions, applied atomically, e.g. `[{\"action\": \"remove\", \"index\": \"products-v1\", \"alias\": \"products\"}, {\"action\": \"add\", \"index\": \"products-v2\", \"alias\": \"products\"}]`"
+    )]
+    pub actions: Vec<AliasAction>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum AliasAction {
+    #[schemars(description = "Add an alias to an index")]
+    Add {
+        index: String,
+        alias: String,
+        #[schemars(
+            description = "Whether the index is the one that receives the writes to the alias"
+        )]
+        is_write_index: Option<bool>,
+        #[schemars(
+            description = "A query clause that filters the documents seen through the alias"
+        )]
+        filter: Option<Value>,
+    },
+    #[schemars(description = "Remove an alias from an index")]
+    Remove { index: String, alias: String },
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SwapAliasRequest {
+    #[schemars(description = "The name of the alias, e.g. `products`")]
+    pub alias: String,
+    #[schemars(description = "The index that the alias is moved to, e.g. `products-v2`")]
+    pub to_index: String,
+    #[schemars(
+        description = "The index that the alias is removed from. Defaults to all the indices of the alias"
+    )]
+    pub from_index: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SwapAliasResponse {
+    pub alias: String,
+    /// indices that the alias was removed from
+    pub removed_from: Vec<String>,
+    /// index that the alias was added to
+    pub added_to: String,
+}
+impl From<rmcp::model::CallToolResult> for SwapAliasResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<SwapAliasResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ReindexRequest {
+    #[schemars(
+        description = "The name of the index, alias or index pattern to copy the documents from"
+    )]
+    pub source_index: String,
+    #[schemars(
+        description = "The name of the index to copy the documents to. It is created with the matching index templates if it does not exist"
+    )]
+    pub dest_index: String,
+    #[schemars(
+        description = "The query clause in the Elasticsearch query DSL that matches the documents to copy. Defaults to all documents"
+    )]
+    pub query: Option<Value>,
+    #[schemars(description = "The maximum number of documents to copy")]
+    pub max_docs: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ReindexResponse {
+    /// id of the reindex task, to pass to the `get_task` tool
+    pub task: String,
+}
+impl From<rmcp::model::CallToolResult> for ReindexResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ReindexResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct TaskRequest {
+    #[schemars(
+        description = "The id of a task returned by the `reindex` tool, e.g. `oTUltX4IQMOUUVeiohTt8A:12345`"
+    )]
+    pub task_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct TaskStatusResponse {
+    pub task_id: String,
+    /// whether the task is completed
+    pub completed: bool,
+    /// action of the task, e.g. `indices:data/write/reindex`
+    pub action: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// running time of the task, in seconds
+    pub running_time_s: f64,
+    /// number of documents to process
+    pub total: u64,
+    pub created: u64,
+    pub updated: u64,
+    pub deleted: u64,
+    /// number of documents that were changed while being processed
+    pub version_conflicts: u64,
+    /// documents that failed, once the task is completed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub failures: Vec<Value>,
+    /// error of the task, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+}
+impl From<rmcp::model::CallToolResult> for TaskStatusResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<TaskStatusResponse>(content).unwrap()
+    }
+}
+
+/// body of an error response of Elasticsearch
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    pub error: ErrorCause,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ErrorCause {
+    /// The type of error
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// A human-readable explanation of the error, in English.
+    pub reason: Option<String>,
+    /// deepest errors that caused the error
+    #[serde(default)]
+    pub root_cause: Vec<ErrorCause>,
+}
+impl fmt::Display for ErrorCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the root cause usually tells more about a malformed query than the error itself
+        let cause = self.root_cause.first().unwrap_or(self);
+        match &cause.reason {
+            Some(reason) => write!(f, "{}: {}", cause.ty, reason),
+            None => write!(f, "{}", cause.ty),
+        }
+    }
+}
@@ -1,14 +1,59 @@
-use crate::CONNECTION_CONFIG;
+use crate::{Auth, CONNECTION_CONFIG, ConnectionConfig, SemanticSearchConfig};
+use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
 use cardea_elastic_mcp_common::*;
+use endpoints::embeddings::{EmbeddingRequest, EmbeddingsResponse, InputText};
+use reqwest::Method;
 use rmcp::{
     ErrorData as McpError, ServerHandler,
     handler::server::{router::tool::ToolRouter, tool::*},
     model::*,
     tool, tool_handler, tool_router,
 };
-use serde_json::{Value, json};
+use serde::{Deserialize, de::DeserializeOwned};
+use serde_json::{Map, Value, json};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
 use tracing::error;
 
+/// The maximum value of `from + size` allowed by the default `index.max_result_window` setting
+const MAX_RESULT_WINDOW: u64 = 10_000;
+/// The default size of the highlighted fragments in characters
+const DEFAULT_FRAGMENT_SIZE: u64 = 150;
+/// The default maximum number of highlighted fragments per field
+const DEFAULT_NUMBER_OF_FRAGMENTS: u64 = 3;
+/// The default number of rows returned by ES|QL and SQL queries
+const DEFAULT_QUERY_ROWS: u64 = 100;
+/// The maximum number of rows returned by ES|QL and SQL queries
+const MAX_QUERY_ROWS: u64 = 1_000;
+/// The maximum number of unassigned shards explained by the `cat_shards` tool
+const MAX_EXPLAINED_SHARDS: usize = 10;
+/// The maximum number of documents returned by the `mget` tool
+const MAX_MGET_IDS: usize = 100;
+/// Maximum number of lines of the explanation of a score
+const MAX_EXPLANATION_LINES: usize = 200;
+/// Maximum depth of the queries of a shard in the summary of a profile
+const MAX_PROFILED_QUERY_DEPTH: usize = 3;
+/// Number of phases reported for each profiled query
+const TOP_PROFILED_PHASES: usize = 3;
+/// How long a point-in-time is kept open between two pages
+const PIT_KEEP_ALIVE: &str = "5m";
+/// How long the state of a cursor is kept, as long as the point-in-time it pages through
+const CURSOR_KEEP_ALIVE: Duration = Duration::from_secs(5 * 60);
+/// Maximum number of hits of a page of a point-in-time search
+const MAX_PAGE_SIZE: u64 = 1_000;
+
+/// The ids of the tasks started by the `reindex` tool. The `get_task` and `cancel_task` tools only
+/// accept these tasks, since the other tasks of the cluster may touch indices that are not allowed
+static STARTED_TASKS: LazyLock<Mutex<HashSet<String>>> = LazyLock::new(Default::default);
+
+/// The state of the cursors returned by the `search_page` tool, by cursor id, with the time they
+/// were created. The state stays on the server, so that a client can't change it
+static PAGE_CURSORS: LazyLock<Mutex<HashMap<String, (Instant, PageCursor)>>> =
+    LazyLock::new(Default::default);
+
 #[derive(Debug, Clone)]
 pub struct ElasticSearchServer {
     tool_router: ToolRouter<Self>,
@@ -16,333 +61,3385 @@ pub struct ElasticSearchServer {
 
 #[tool_router]
 impl ElasticSearchServer {
-    pub fn new() -> Self {
-        Self {
-            tool_router: Self::tool_router(),
+    pub fn new(allow_write: bool, enable_semantic_search: bool, enable_admin_tools: bool) -> Self {
+        let mut tool_router = Self::tool_router();
+        if allow_write {
+            tool_router += Self::write_tool_router();
         }
+        if enable_admin_tools {
+            tool_router += Self::admin_tool_router();
+        }
+        if enable_semantic_search {
+            tool_router += Self::semantic_tool_router();
+        }
+
+        Self { tool_router }
     }
 
     #[tool(description = "List all available Elasticsearch indices")]
     async fn list_indices(&self) -> Result<CallToolResult, McpError> {
-        // get connection config
-        let conn_config = match CONNECTION_CONFIG.get() {
-            Some(connection_config) => {
-                let conn_config = connection_config.read().await;
-                conn_config.clone()
+        let conn_config = get_connection_config().await?;
+
+        let indices: Vec<IndexInfo> = send_request(
+            &conn_config,
+            Method::GET,
+            "_cat/indices?v=true&s=index&format=json",
+            None,
+            "list indices",
+        )
+        .await?;
+
+        let content = Content::json(ListIndicesResponse { indices })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get the cluster's index aliases, including filter and routing information. Note that this tool does not return data stream aliases."
+    )]
+    async fn list_aliases(&self) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let aliases: Vec<AliasInfo> = send_request(
+            &conn_config,
+            Method::GET,
+            "_cat/aliases?format=json&v=true",
+            None,
+            "list aliases",
+        )
+        .await?;
+
+        let content = Content::json(ListAliasesResponse { aliases })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get the mapping of the index: the fields and their types, e.g. to find the fields to search, filter or aggregate on"
+    )]
+    async fn get_mapping(
+        &self,
+        Parameters(GetMappingRequest { index }): Parameters<GetMappingRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let index = &resolve_index(index, &conn_config)?;
+        let mappings: Map<String, Value> = send_request(
+            &conn_config,
+            Method::GET,
+            &format!("{index}/_mapping"),
+            None,
+            "get mapping",
+        )
+        .await?;
+
+        let indices = mappings
+            .into_iter()
+            .map(|(index, mapping)| {
+                let mut fields = Vec::new();
+                if let Some(properties) = mapping["mappings"]["properties"].as_object() {
+                    flatten_properties(properties, "", &mut fields);
+                }
+                IndexMapping { index, fields }
+            })
+            .collect();
+
+        let content = Content::json(GetMappingResponse { indices })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get the settings of the index, e.g. the number of shards and replicas, and the analyzers"
+    )]
+    async fn get_settings(
+        &self,
+        Parameters(GetSettingsRequest { index }): Parameters<GetSettingsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let index = &resolve_index(index, &conn_config)?;
+        let settings: Map<String, Value> = send_request(
+            &conn_config,
+            Method::GET,
+            &format!("{index}/_settings?flat_settings=true"),
+            None,
+            "get settings",
+        )
+        .await?;
+
+        let indices = settings
+            .into_iter()
+            .map(|(index, settings)| IndexSettings {
+                index,
+                settings: settings["settings"]
+                    .as_object()
+                    .cloned()
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        let content = Content::json(GetSettingsResponse { indices })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get the capabilities of the fields of the index: their type, and whether they can be searched and aggregated on"
+    )]
+    async fn field_caps(
+        &self,
+        Parameters(FieldCapsRequest { index, fields }): Parameters<FieldCapsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let index = &resolve_index(index, &conn_config)?;
+        let fields = get_field_caps(&conn_config, index, fields.as_deref()).await?;
+
+        let content = Content::json(FieldCapsResponse { fields })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get the health of the cluster: the status (green, yellow or red), the number of nodes and the number of active, initializing and unassigned shards"
+    )]
+    async fn cluster_health(&self) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let health: ClusterHealthResponse = send_request(
+            &conn_config,
+            Method::GET,
+            "_cluster/health",
+            None,
+            "get cluster health",
+        )
+        .await?;
+
+        let content = Content::json(health)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get a summary of the cluster statistics: the nodes by role and version, the number of indices, shards and documents, the store size, and the heap and disk usage"
+    )]
+    async fn cluster_stats(&self) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let stats: Value = send_request(
+            &conn_config,
+            Method::GET,
+            "_cluster/stats",
+            None,
+            "get cluster stats",
+        )
+        .await?;
+
+        let nodes = stats["nodes"]["count"]
+            .as_object()
+            .map(|count| {
+                count
+                    .iter()
+                    .filter_map(|(role, count)| Some((role.clone(), count.as_u64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let versions = stats["nodes"]["versions"]
+            .as_array()
+            .map(|versions| {
+                versions
+                    .iter()
+                    .filter_map(|version| version.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let summary = ClusterStatsResponse {
+            cluster_name: stats["cluster_name"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            status: stats["status"].as_str().unwrap_or_default().to_string(),
+            nodes,
+            versions,
+            indices_count: u64_at(&stats, "/indices/count").unwrap_or_default(),
+            shards_total: u64_at(&stats, "/indices/shards/total").unwrap_or_default(),
+            shards_primaries: u64_at(&stats, "/indices/shards/primaries").unwrap_or_default(),
+            docs_count: u64_at(&stats, "/indices/docs/count").unwrap_or_default(),
+            docs_deleted: u64_at(&stats, "/indices/docs/deleted").unwrap_or_default(),
+            store_size_in_bytes: u64_at(&stats, "/indices/store/size_in_bytes").unwrap_or_default(),
+            heap_used_in_bytes: u64_at(&stats, "/nodes/jvm/mem/heap_used_in_bytes")
+                .unwrap_or_default(),
+            heap_max_in_bytes: u64_at(&stats, "/nodes/jvm/mem/heap_max_in_bytes")
+                .unwrap_or_default(),
+            disk_total_in_bytes: u64_at(&stats, "/nodes/fs/total_in_bytes").unwrap_or_default(),
+            disk_available_in_bytes: u64_at(&stats, "/nodes/fs/available_in_bytes")
+                .unwrap_or_default(),
+        };
+
+        let content = Content::json(summary)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get a summary of the statistics of each node: the roles, the CPU, load, heap and disk usage, the number of documents, and the search and indexing totals"
+    )]
+    async fn nodes_stats(&self) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let stats: Value = send_request(
+            &conn_config,
+            Method::GET,
+            "_nodes/stats/os,jvm,fs,indices",
+            None,
+            "get nodes stats",
+        )
+        .await?;
+
+        let mut nodes: Vec<NodeStats> = stats["nodes"]
+            .as_object()
+            .map(|nodes| nodes.values().map(summarize_node_stats).collect())
+            .unwrap_or_default();
+        nodes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let content = Content::json(NodesStatsResponse { nodes })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get the statistics of the index: the number of documents, the store size, and the indexing and search totals"
+    )]
+    async fn index_stats(
+        &self,
+        Parameters(IndexStatsRequest { index }): Parameters<IndexStatsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let index = &resolve_index(index, &conn_config)?;
+        let stats: Value = send_request(
+            &conn_config,
+            Method::GET,
+            &format!("{index}/_stats/docs,store,indexing,search"),
+            None,
+            "get index stats",
+        )
+        .await?;
+
+        let indices = stats["indices"]
+            .as_object()
+            .map(|indices| {
+                indices
+                    .iter()
+                    .map(|(index, stats)| IndexStats {
+                        index: index.clone(),
+                        docs_count: u64_at(stats, "/primaries/docs/count").unwrap_or_default(),
+                        docs_deleted: u64_at(stats, "/primaries/docs/deleted").unwrap_or_default(),
+                        primaries_store_size_in_bytes: u64_at(
+                            stats,
+                            "/primaries/store/size_in_bytes",
+                        )
+                        .unwrap_or_default(),
+                        total_store_size_in_bytes: u64_at(stats, "/total/store/size_in_bytes")
+                            .unwrap_or_default(),
+                        indexing_index_total: u64_at(stats, "/primaries/indexing/index_total")
+                            .unwrap_or_default(),
+                        indexing_index_time_in_millis: u64_at(
+                            stats,
+                            "/primaries/indexing/index_time_in_millis",
+                        )
+                        .unwrap_or_default(),
+                        search_query_total: u64_at(stats, "/total/search/query_total")
+                            .unwrap_or_default(),
+                        search_query_time_in_millis: u64_at(
+                            stats,
+                            "/total/search/query_time_in_millis",
+                        )
+                        .unwrap_or_default(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let content = Content::json(IndexStatsResponse { indices })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "List the shards of the indices with their state, size and node. The unassigned shards come with the explanation of why they can not be allocated"
+    )]
+    async fn cat_shards(
+        &self,
+        Parameters(CatShardsRequest { index }): Parameters<CatShardsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let path = match &index {
+            Some(index) => {
+                check_indices_allowed(index.split(','), &conn_config)?;
+                format!("_cat/shards/{index}")
+            }
+            None => "_cat/shards".to_string(),
+        };
+        let mut shards: Vec<ShardInfo> = send_request(
+            &conn_config,
+            Method::GET,
+            &format!(
+                "{path}?format=json&s=state,index,shard&h=index,shard,prirep,state,docs,store,node,unassigned.reason"
+            ),
+            None,
+            "list shards",
+        )
+        .await?;
+        // without an index, only the shards of the allowed indices are listed
+        if index.is_none() {
+            shards.retain(|shard| {
+                conn_config
+                    .allowed_indices
+                    .iter()
+                    .any(|pattern| matches_pattern(pattern, &shard.index))
+            });
+        }
+
+        // explaining an allocation is costly, so only the first unassigned shards are explained
+        for shard in shards
+            .iter_mut()
+            .filter(|shard| shard.state == "UNASSIGNED")
+            .take(MAX_EXPLAINED_SHARDS)
+        {
+            let body = json!({
+                "index": shard.index,
+                "shard": shard.shard.parse::<u64>().unwrap_or_default(),
+                "primary": shard.prirep == "p",
+            });
+            match send_request::<Value>(
+                &conn_config,
+                Method::POST,
+                "_cluster/allocation/explain",
+                Some(body),
+                "explain shard allocation",
+            )
+            .await
+            {
+                Ok(explanation) => shard.explanation = Some(summarize_allocation(&explanation)),
+                Err(e) => tracing::warn!("{}", e.message),
+            }
+        }
+
+        let content = Content::json(CatShardsResponse { shards })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Get a document by its id, e.g. a document found by an earlier search")]
+    async fn get_document(
+        &self,
+        Parameters(GetDocumentRequest {
+            index,
+            id,
+            source_includes,
+            source_excludes,
+        }): Parameters<GetDocumentRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let index = resolve_index(index, &conn_config)?;
+        let docs = multi_get(
+            &conn_config,
+            &index,
+            &[id],
+            source_filter(source_includes, source_excludes),
+            "get document",
+        )
+        .await?;
+
+        match docs.into_iter().next() {
+            Some(document) => {
+                let content = Content::json(document)?;
+
+                Ok(CallToolResult::success(vec![content]))
             }
             None => {
-                let error_message = "Connection config not found";
+                let error_message = "Failed to get document: no document returned";
                 error!("{}", error_message);
-                return Err(McpError::new(
+                Err(McpError::new(
                     ErrorCode::INTERNAL_ERROR,
                     error_message,
                     None,
-                ));
+                ))
             }
-        };
+        }
+    }
 
-        // build url
-        let base_url = conn_config.base_url.trim_end_matches('/');
-        let url = format!("{base_url}/_cat/indices?v=true&s=index&format=json");
+    #[tool(description = "Get many documents by their ids at once")]
+    async fn mget(
+        &self,
+        Parameters(MgetRequest {
+            index,
+            ids,
+            source_includes,
+            source_excludes,
+        }): Parameters<MgetRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
 
-        // get api key
-        let api_key = conn_config.api_key;
+        if ids.is_empty() || ids.len() > MAX_MGET_IDS {
+            let error_message = format!("Between 1 and {MAX_MGET_IDS} ids must be given");
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
 
-        let client = reqwest::Client::new();
-        let result = match api_key {
-            Some(api_key) => {
-                client
-                    .get(&url)
-                    .header("Authorization", api_key)
-                    .send()
-                    .await
-            }
-            None => client.get(&url).send().await,
-        };
+        let index = resolve_index(index, &conn_config)?;
+        let docs = multi_get(
+            &conn_config,
+            &index,
+            &ids,
+            source_filter(source_includes, source_excludes),
+            "get documents",
+        )
+        .await?;
 
-        let response = match result {
-            Ok(response) => response,
-            Err(e) => {
-                let error_message = format!("Failed to list indices: {e}");
+        let content = Content::json(MgetResponse { docs })?;
 
-                error!(error_message);
+        Ok(CallToolResult::success(vec![content]))
+    }
 
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
+    #[tool(
+        description = "Count the documents that match a query, e.g. to know how many hits there are before paging through them"
+    )]
+    async fn count(
+        &self,
+        Parameters(CountRequest { index, query }): Parameters<CountRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let body = match query {
+            Some(query) => {
+                validate_query_clause(&query).map_err(|error_message| {
+                    error!("{}", error_message);
+                    McpError::invalid_params(error_message, None)
+                })?;
+                Some(json!({ "query": query }))
             }
+            None => None,
         };
 
-        match response.status().is_success() {
-            true => match response.json::<Value>().await {
-                Ok(json) => {
-                    let indices: Vec<IndexInfo> = serde_json::from_value(json).map_err(|e| {
-                        let error_message = format!("Failed to parse indices: {e}");
+        let index = resolve_index(index, &conn_config)?;
+        let response: CountResponse = send_request(
+            &conn_config,
+            Method::POST,
+            &format!("{index}/_count"),
+            body,
+            "count documents",
+        )
+        .await?;
 
-                        error!(error_message);
+        let content = Content::json(response)?;
 
-                        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
-                    })?;
+        Ok(CallToolResult::success(vec![content]))
+    }
 
-                    let content = Content::json(ListIndicesResponse { indices })?;
+    #[tool(description = "Perform a keyword search")]
+    async fn search(
+        &self,
+        Parameters(SearchRequest {
+            query,
+            index,
+            fields,
+            size,
+            from,
+            mode,
+            highlight,
+            fragment_size,
+            number_of_fragments,
+            source_includes,
+            source_excludes,
+            format,
+            profile,
+        }): Parameters<SearchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
 
-                    Ok(CallToolResult::success(vec![content]))
-                }
-                Err(e) => {
-                    let error_message = format!("Failed to parse indices: {e}");
+        let index = &resolve_index(index, &conn_config)?;
+        let fields = &resolve_fields(fields, &conn_config)?;
+        let size = size.unwrap_or(conn_config.size);
+        let from = from.unwrap_or(0);
+        if from.checked_add(size).is_none_or(|n| n > MAX_RESULT_WINDOW) {
+            let error_message = format!(
+                "`from` + `size` must not be greater than {MAX_RESULT_WINDOW}. Use the `search_page` tool to page through more hits"
+            );
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+        let mode = mode.unwrap_or_default();
+        let format = format.unwrap_or_default();
+        let profile = profile.unwrap_or(false);
 
-                    error!(error_message);
+        tracing::info!("index: {}", index);
+        tracing::info!("fields: {:?}", fields);
+        tracing::info!("size: {}", size);
+        tracing::info!("mode: {:?}", mode);
+        tracing::info!("format: {:?}", format);
 
-                    Err(McpError::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        error_message,
-                        None,
-                    ))
-                }
-            },
-            false => {
-                let error_message = format!("Failed to list indices: {}", response.status());
+        let mut body = json!({
+            "query": keyword_query(&query, fields, mode),
+            "size": size
+        });
+        if from > 0 {
+            body["from"] = json!(from);
+        }
+        if profile {
+            body["profile"] = json!(true);
+        }
 
-                error!(error_message);
+        if highlight.unwrap_or(false) || matches!(format, ResultFormat::Text) {
+            let fragment_size = fragment_size.unwrap_or(DEFAULT_FRAGMENT_SIZE);
+            let highlight_fields: Map<String, Value> = fields
+                .iter()
+                .map(|field| {
+                    // the fields may have a boost, e.g. `title^2`
+                    let name = field.split('^').next().unwrap_or(field);
+                    (name.to_string(), json!({}))
+                })
+                .collect();
 
-                Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ))
+            body["highlight"] = json!({
+                "fields": highlight_fields,
+                "fragment_size": fragment_size,
+                "number_of_fragments": number_of_fragments.unwrap_or(DEFAULT_NUMBER_OF_FRAGMENTS),
+            });
+            if let ResultFormat::Text = format {
+                // return the beginning of the fields that do not match, and mark the matches in
+                // markdown
+                body["highlight"]["no_match_size"] = json!(fragment_size);
+                body["highlight"]["pre_tags"] = json!(["**"]);
+                body["highlight"]["post_tags"] = json!(["**"]);
+            }
+        }
+
+        match format {
+            // only the title is shown besides the fragments
+            ResultFormat::Text => {
+                body["_source"] = json!({ "includes": [conn_config.title_field] })
+            }
+            ResultFormat::Json => {
+                if let Some(source) = source_filter(source_includes, source_excludes) {
+                    body["_source"] = source;
+                }
             }
         }
+
+        let ProfiledSearchResponse {
+            response: search_response,
+            profile,
+        } = send_request(
+            &conn_config,
+            Method::POST,
+            &format!("{index}/_search"),
+            Some(body),
+            "search",
+        )
+        .await?;
+
+        let mut contents = vec![match format {
+            ResultFormat::Json => Content::json(search_response)?,
+            ResultFormat::Text => Content::text(render_hits_as_text(
+                &search_response,
+                &conn_config.title_field,
+            )),
+        }];
+        if let Some(profile) = profile {
+            contents.push(Content::json(summarize_profile(&profile))?);
+        }
+
+        Ok(CallToolResult::success(contents))
     }
 
     #[tool(
-        description = "Get the cluster's index aliases, including filter and routing information. Note that this tool does not return data stream aliases."
+        description = "Explain why a document matches a query or not, and how its score is computed, e.g. to tune the boosts of the fields of the `search` tool"
     )]
-    async fn list_aliases(&self) -> Result<CallToolResult, McpError> {
-        // get connection config
-        let conn_config = match CONNECTION_CONFIG.get() {
-            Some(connection_config) => {
-                let conn_config = connection_config.read().await;
-                conn_config.clone()
+    async fn explain_document(
+        &self,
+        Parameters(ExplainDocumentRequest {
+            index,
+            id,
+            query,
+            fields,
+            mode,
+            query_dsl,
+        }): Parameters<ExplainDocumentRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let index = resolve_index(index, &conn_config)?;
+        let query = match (query, query_dsl) {
+            (Some(query), None) => {
+                let fields = resolve_fields(fields, &conn_config)?;
+                keyword_query(&query, &fields, mode.unwrap_or_default())
             }
-            None => {
-                let error_message = "Connection config not found";
+            (None, Some(query_dsl)) => {
+                validate_query_clause(&query_dsl).map_err(|error_message| {
+                    error!("{}", error_message);
+                    McpError::invalid_params(error_message, None)
+                })?;
+                query_dsl
+            }
+            _ => {
+                let error_message = "Exactly one of `query` and `query_dsl` must be given";
                 error!("{}", error_message);
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
+                return Err(McpError::invalid_params(error_message, None));
             }
         };
 
-        // build url
-        let base_url = conn_config.base_url.trim_end_matches('/');
-        let url = format!("{base_url}/_cat/aliases?format=json&v=true");
+        let response: ExplainResult = send_request(
+            &conn_config,
+            Method::POST,
+            &format!("{index}/_explain/{}", encode_path_segment(&id)),
+            Some(json!({ "query": query })),
+            "explain document",
+        )
+        .await?;
 
-        // get api key
-        let api_key = conn_config.api_key;
+        let mut lines = Vec::new();
+        if let Some(explanation) = &response.explanation {
+            render_explanation(explanation, 0, &mut lines);
+        }
+        if lines.len() > MAX_EXPLANATION_LINES {
+            let omitted = lines.len() - MAX_EXPLANATION_LINES;
+            lines.truncate(MAX_EXPLANATION_LINES);
+            lines.push(format!("... {omitted} more lines"));
+        }
 
-        let client = reqwest::Client::new();
-        let result = match api_key {
-            Some(api_key) => {
-                client
-                    .get(&url)
-                    .header("Authorization", api_key)
-                    .send()
-                    .await
-            }
-            None => client.get(&url).send().await,
+        let score = match response.matched {
+            true => response
+                .explanation
+                .as_ref()
+                .map(|explanation| explanation.value),
+            false => None,
+        };
+        let response = ExplainDocumentResponse {
+            index: response.index,
+            id: response.id,
+            matched: response.matched,
+            score,
+            explanation: lines.join("\n"),
         };
 
-        let response = match result {
-            Ok(response) => response,
-            Err(e) => {
-                let error_message = format!("Failed to list aliases: {e}");
+        let content = Content::json(response)?;
 
-                error!(error_message);
+        Ok(CallToolResult::success(vec![content]))
+    }
 
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
-            }
-        };
+    #[tool(
+        description = "Perform a read-only search with the Elasticsearch query DSL, e.g. phrase, boolean, range and fuzzy queries. Only `query`, `sort`, `_source`, `from`, `size` and `search_after` are accepted. To page through the hits, pass the `sort` values of the last hit as `search_after`"
+    )]
+    async fn search_dsl(
+        &self,
+        Parameters(mut request): Parameters<DslSearchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
 
-        match response.status().is_success() {
-            true => match response.json::<Value>().await {
-                Ok(json) => {
-                    let aliases: Vec<AliasInfo> = serde_json::from_value(json).map_err(|e| {
-                        let error_message = format!("Failed to parse aliases: {e}");
+        let index = resolve_index(request.index.take(), &conn_config)?;
+        let body = build_dsl_body(request, conn_config.size).map_err(|error_message| {
+            error!("{}", error_message);
+            McpError::invalid_params(error_message, None)
+        })?;
 
-                        error!(error_message);
+        tracing::debug!(
+            "search body:\n{}",
+            serde_json::to_string_pretty(&body).unwrap_or_default()
+        );
 
-                        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
-                    })?;
+        let search_response: SearchResponse = send_request(
+            &conn_config,
+            Method::POST,
+            &format!("{index}/_search"),
+            Some(body),
+            "search",
+        )
+        .await?;
+
+        let content = Content::json(search_response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Page through all the hits of a search, including beyond the first 10,000 hits. The first call opens a point-in-time of the index and returns the first page with a `cursor`. Pass the cursor alone to get the next page. The cursor is `null` on the last page, and expires if it is not used for 5 minutes"
+    )]
+    async fn search_page(
+        &self,
+        Parameters(PageSearchRequest {
+            cursor,
+            index,
+            query,
+            sort,
+            source_includes,
+            source_excludes,
+            size,
+        }): Parameters<PageSearchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
 
-                    let content = Content::json(ListAliasesResponse { aliases })?;
+        let cursor = match cursor {
+            Some(cursor) => PageCursor::take(&cursor)?,
+            None => {
+                let index = resolve_index(index, &conn_config)?;
+                if let Some(query) = &query {
+                    validate_query_clause(query).map_err(|error_message| {
+                        error!("{}", error_message);
+                        McpError::invalid_params(error_message, None)
+                    })?;
+                }
+                let size = size.unwrap_or(conn_config.size);
+                validate_page_size(size)?;
 
-                    Ok(CallToolResult::success(vec![content]))
+                let pit_id = open_pit(&conn_config, &index).await?;
+                PageCursor {
+                    pit_id,
+                    search_after: None,
+                    query,
+                    // the hits must be sorted to page with `search_after`. Elasticsearch adds a
+                    // tiebreaker to the sort of a point-in-time search
+                    sort: Some(sort.unwrap_or_else(|| json!([{ "_score": "desc" }]))),
+                    source: source_filter(source_includes, source_excludes),
+                    size,
                 }
-                Err(e) => {
-                    let error_message = format!("Failed to parse aliases: {e}");
+            }
+        };
 
-                    error!(error_message);
+        let page = search_pit(&conn_config, &cursor).await?;
 
-                    Err(McpError::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        error_message,
-                        None,
-                    ))
+        // a page that is not full is the last one
+        let next_cursor = match page.hits.hits.len() as u64 == cursor.size {
+            true => Some(
+                PageCursor {
+                    pit_id: page.pit_id,
+                    search_after: page.hits.hits.last().and_then(|hit| hit.sort.clone()),
+                    ..cursor
                 }
-            },
+                .store(),
+            ),
             false => {
-                let error_message = format!("Failed to list aliases: {}", response.status());
+                close_pit(&conn_config, &page.pit_id).await;
+                None
+            }
+        };
 
-                error!(error_message);
+        let response = PageSearchResponse {
+            total: page.hits.total,
+            hits: page.hits.hits,
+            cursor: next_cursor,
+        };
 
-                Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ))
-            }
-        }
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
     }
 
-    #[tool(description = "Perform a keyword search")]
-    async fn search(
+    #[tool(
+        description = "Export all the documents that match a query, up to the row limit of the server, as NDJSON or CSV. The first content is a summary with the number of exported rows and whether the export was truncated, the second content is the exported data"
+    )]
+    async fn export(
         &self,
-        Parameters(SearchRequest { query }): Parameters<SearchRequest>,
+        Parameters(ExportRequest {
+            index,
+            query,
+            sort,
+            fields,
+            format,
+            max_rows,
+        }): Parameters<ExportRequest>,
     ) -> Result<CallToolResult, McpError> {
-        // get connection config
-        let conn_config = match CONNECTION_CONFIG.get() {
-            Some(connection_config) => {
-                let conn_config = connection_config.read().await;
-                conn_config.clone()
-            }
-            None => {
-                let error_message = "Connection config not found";
+        let conn_config = get_connection_config().await?;
+
+        let index = resolve_index(index, &conn_config)?;
+        if let Some(query) = &query {
+            validate_query_clause(query).map_err(|error_message| {
                 error!("{}", error_message);
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
+                McpError::invalid_params(error_message, None)
+            })?;
+        }
+        let invalid_fields = fields
+            .as_ref()
+            .is_some_and(|fields| fields.is_empty() || fields.iter().any(String::is_empty));
+        if invalid_fields {
+            let error_message = "`fields` must be a non-empty list of field names";
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+        let max_rows = match max_rows {
+            Some(max_rows) if max_rows == 0 || max_rows > conn_config.max_export_rows => {
+                let error_message = format!(
+                    "`max_rows` must be between 1 and {}",
+                    conn_config.max_export_rows
+                );
+                error!("{}", error_message);
+                return Err(McpError::invalid_params(error_message, None));
             }
+            Some(max_rows) => max_rows,
+            None => conn_config.max_export_rows,
         };
-
-        let index = conn_config.index;
-        let fields = conn_config.fields;
-        let size = conn_config.size;
+        let format = format.unwrap_or_default();
 
         tracing::info!("index: {}", index);
-        tracing::info!("fields: {:?}", fields);
-        tracing::info!("size: {}", size);
+        tracing::info!("max rows: {}", max_rows);
+        tracing::info!("format: {:?}", format);
 
-        // build url
-        let base_url = conn_config.base_url.trim_end_matches('/');
-        let url = format!("{base_url}/{index}/_search");
+        let pit_id = open_pit(&conn_config, &index).await?;
+        let mut cursor = PageCursor {
+            pit_id,
+            search_after: None,
+            query,
+            // the index order is the cheapest sort
+            sort: Some(sort.unwrap_or_else(|| json!([{ "_shard_doc": "asc" }]))),
+            source: fields.as_ref().map(|fields| json!({ "includes": fields })),
+            size: 0,
+        };
 
-        // get api key
-        let api_key = conn_config.api_key;
+        // one more hit than the limit is fetched to know whether the export is truncated
+        let mut hits: Vec<Hit> = Vec::new();
+        let result = loop {
+            cursor.size = (max_rows + 1 - hits.len() as u64).min(MAX_PAGE_SIZE);
+            let page = match search_pit(&conn_config, &cursor).await {
+                Ok(page) => page,
+                Err(e) => break Err(e),
+            };
 
-        let body = json!({
-            "query": {
-                "multi_match": {
-                    "query": query,
-                    "fields": fields
-                },
-            },
-            "size": size
-        });
+            let count = page.hits.hits.len() as u64;
+            cursor.pit_id = page.pit_id;
+            cursor.search_after = page.hits.hits.last().and_then(|hit| hit.sort.clone());
+            hits.extend(page.hits.hits);
 
-        let client = reqwest::Client::new();
-        let result = match api_key {
-            Some(api_key) => {
-                client
-                    .post(&url)
-                    .header("Authorization", api_key)
-                    .header("Content-Type", "application/json")
-                    .json(&body)
-                    .send()
-                    .await
-            }
-            None => {
-                client
-                    .post(&url)
-                    .header("Content-Type", "application/json")
-                    .json(&body)
-                    .send()
-                    .await
+            if count < cursor.size || hits.len() as u64 > max_rows {
+                break Ok(());
             }
         };
+        close_pit(&conn_config, &cursor.pit_id).await;
+        result?;
 
-        let response = match result {
-            Ok(response) => response,
-            Err(e) => {
-                let error_message = format!("Failed to search: {e}");
+        let truncated = hits.len() as u64 > max_rows;
+        hits.truncate(max_rows as usize);
 
-                error!(error_message);
-
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
-            }
+        let data = match format {
+            ExportFormat::Ndjson => render_hits_as_ndjson(&hits),
+            ExportFormat::Csv => render_hits_as_csv(&hits, fields.as_deref()),
         };
 
-        match response.status().is_success() {
-            true => match response.json::<Value>().await {
-                Ok(json) => {
-                    tracing::debug!(
-                        "search result json:\n{}",
-                        serde_json::to_string_pretty(&json).unwrap()
-                    );
+        let response = ExportResponse {
+            format,
+            rows: hits.len() as u64,
+            truncated,
+        };
 
-                    let search_response: SearchResponse =
-                        serde_json::from_value(json).map_err(|e| {
-                            let error_message = format!("Failed to parse search result: {e}");
+        Ok(CallToolResult::success(vec![
+            Content::json(response)?,
+            Content::text(data),
+        ]))
+    }
 
-                            error!(error_message);
+    #[tool(
+        description = "Aggregate the documents of the index, e.g. count the documents per value of a field or per day, or compute the stats of a numeric field. Aggregations can be nested with `sub_aggregations`, e.g. the errors per service per day. Each aggregation is returned as a table of flattened rows"
+    )]
+    async fn aggregate(
+        &self,
+        Parameters(AggregateRequest {
+            index,
+            query,
+            aggregations,
+        }): Parameters<AggregateRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
 
-                            McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
-                        })?;
+        let index = resolve_index(index, &conn_config)?;
 
-                    let content = Content::json(search_response)?;
+        let mut body = json!({
+            "size": 0,
+            "aggs": build_aggregations(&aggregations).map_err(|error_message| {
+                error!("{}", error_message);
+                McpError::invalid_params(error_message, None)
+            })?,
+        });
+        if let Some(query) = query {
+            validate_query_clause(&query).map_err(|error_message| {
+                error!("{}", error_message);
+                McpError::invalid_params(error_message, None)
+            })?;
+            body["query"] = query;
+        }
 
-                    Ok(CallToolResult::success(vec![content]))
-                }
-                Err(e) => {
-                    let error_message = format!("Failed to parse search result: {e}");
+        tracing::debug!(
+            "aggregate body:\n{}",
+            serde_json::to_string_pretty(&body).unwrap_or_default()
+        );
 
-                    error!(error_message);
+        let response: AggregationSearchResponse = send_request(
+            &conn_config,
+            Method::POST,
+            &format!("{index}/_search"),
+            Some(body),
+            "aggregate",
+        )
+        .await?;
 
-                    Err(McpError::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        error_message,
-                        None,
-                    ))
+        let aggregations = aggregations
+            .iter()
+            .map(|aggregation| {
+                let mut rows = Vec::new();
+                flatten_aggregations(
+                    std::slice::from_ref(aggregation),
+                    &response.aggregations,
+                    Map::new(),
+                    &mut rows,
+                );
+                AggregationTable {
+                    name: aggregation.name.clone(),
+                    rows,
                 }
-            },
-            false => {
-                let error_message = format!("Failed to search: {}", response.status());
+            })
+            .collect();
 
-                error!(error_message);
+        let content = Content::json(AggregateResponse {
+            took: response.took,
+            total: response.hits.total,
+            aggregations,
+        })?;
 
-                Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Run a read-only ES|QL query, e.g. `FROM logs-* | WHERE level == \"error\" | STATS errors = COUNT(*) BY service`. Returns the columns and the rows of the result"
+    )]
+    async fn esql_query(
+        &self,
+        Parameters(EsqlQueryRequest { query, limit }): Parameters<EsqlQueryRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let limit = query_row_limit(limit)?;
+
+        // ES|QL has no commands that write, but the query must start with a source command
+        let query = query.trim().trim_end_matches(';');
+        let command = first_keyword(query);
+        if !matches!(command.as_str(), "FROM" | "ROW" | "SHOW") {
+            let error_message =
+                format!("The ES|QL query must start with `FROM`, `ROW` or `SHOW`, not `{command}`");
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+        check_indices_allowed(esql_indices(query), &conn_config)?;
+
+        // ask for one more row than the limit to know if the result is truncated
+        let body = json!({ "query": format!("{query}\n| LIMIT {}", limit + 1) });
+
+        let response: EsqlResult = send_request(
+            &conn_config,
+            Method::POST,
+            "_query",
+            Some(body),
+            "run ES|QL query",
+        )
+        .await?;
+
+        let content = Content::json(tabular_response(response.columns, response.values, limit))?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Run a read-only Elasticsearch SQL query, e.g. `SELECT service, COUNT(*) AS errors FROM \"logs-*\" WHERE level = 'error' GROUP BY service`. Returns the columns and the rows of the result"
+    )]
+    async fn sql_query(
+        &self,
+        Parameters(SqlQueryRequest { query, limit }): Parameters<SqlQueryRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let limit = query_row_limit(limit)?;
+
+        // Elasticsearch SQL is read-only, but only single `SELECT` statements are accepted: the
+        // indices of `SHOW` and `DESCRIBE` statements can't be checked against the allow-list
+        let query = query.trim().trim_end_matches(';').trim_end();
+        let statement = first_keyword(query);
+        if statement != "SELECT" {
+            let error_message = format!(
+                "Only `SELECT` statements are allowed, not `{statement}`. Use the `get_mapping` tool to describe an index"
+            );
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+        if query.contains(';') {
+            let error_message = "Only a single SQL statement is allowed";
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+        check_indices_allowed(sql_indices(query), &conn_config)?;
+
+        // ask for one more row than the limit to know if the result is truncated
+        let body = json!({
+            "query": query,
+            "fetch_size": limit + 1,
+        });
+
+        let response: SqlResult = send_request(
+            &conn_config,
+            Method::POST,
+            "_sql?format=json",
+            Some(body),
+            "run SQL query",
+        )
+        .await?;
+
+        // the remaining pages are not fetched, so free the cursor
+        let truncated_by_cursor = response.cursor.is_some();
+        if let Some(cursor) = response.cursor {
+            let result = send_request::<Value>(
+                &conn_config,
+                Method::POST,
+                "_sql/close",
+                Some(json!({ "cursor": cursor })),
+                "close SQL cursor",
+            )
+            .await;
+            if let Err(e) = result {
+                tracing::warn!("{}", e.message);
+            }
+        }
+
+        let mut response = tabular_response(response.columns, response.rows, limit);
+        response.truncated |= truncated_by_cursor;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+}
+
+/// Tools that search by the embeddings of the documents. Only enabled if an embedding service and
+/// a vector field are configured.
+#[tool_router(router = semantic_tool_router)]
+impl ElasticSearchServer {
+    #[tool(
+        description = "Perform a semantic search: find the documents whose meaning is close to the query, optionally combined with a keyword search"
+    )]
+    async fn semantic_search(
+        &self,
+        Parameters(SemanticSearchRequest {
+            query,
+            mode,
+            filter,
+            size,
+            num_candidates,
+        }): Parameters<SemanticSearchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let Some(semantic_config) = &conn_config.semantic_search else {
+            let error_message = "Semantic search is not configured";
+            error!("{}", error_message);
+            return Err(McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                error_message,
+                None,
+            ));
+        };
+
+        if let Some(filter) = &filter {
+            validate_query_clause(filter).map_err(|error_message| {
+                error!("{}", error_message);
+                McpError::invalid_params(error_message, None)
+            })?;
+        }
+
+        let size = size.unwrap_or(conn_config.size);
+        let num_candidates =
+            num_candidates.unwrap_or(size.saturating_mul(10).clamp(100, MAX_RESULT_WINDOW));
+        if size > num_candidates || num_candidates > MAX_RESULT_WINDOW {
+            let error_message = format!(
+                "`size` must not be greater than `num_candidates`, and `num_candidates` must not be greater than {MAX_RESULT_WINDOW}"
+            );
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+
+        let embedding = compute_embedding(semantic_config, &query).await?;
+
+        let vector_field = &semantic_config.vector_field;
+        let mut knn = json!({
+            "field": vector_field,
+            "query_vector": embedding,
+            "k": size,
+            "num_candidates": num_candidates,
+        });
+        if let Some(filter) = &filter {
+            knn["filter"] = filter.clone();
+        }
+
+        let mut body = match mode.unwrap_or_default() {
+            SemanticMode::Knn => json!({ "knn": knn }),
+            SemanticMode::Hybrid => {
+                let mut standard = json!({
+                    "query": {
+                        "multi_match": {
+                            "query": query,
+                            "fields": conn_config.fields
+                        }
+                    }
+                });
+                if let Some(filter) = filter {
+                    standard["filter"] = filter;
+                }
+
+                json!({
+                    "retriever": {
+                        "rrf": {
+                            "retrievers": [
+                                { "standard": standard },
+                                { "knn": knn }
+                            ],
+                            "rank_window_size": num_candidates.min(MAX_RESULT_WINDOW),
+                        }
+                    }
+                })
+            }
+        };
+        body["size"] = json!(size);
+        // the embeddings are of no use in the results
+        body["_source"] = json!({ "excludes": [vector_field] });
+
+        let index = &conn_config.index;
+        let search_response: SearchResponse = send_request(
+            &conn_config,
+            Method::POST,
+            &format!("{index}/_search"),
+            Some(body),
+            "semantic search",
+        )
+        .await?;
+
+        let content = Content::json(search_response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+}
+
+/// Tools that create indices and write documents. Only enabled with `--allow-write`.
+#[tool_router(router = write_tool_router)]
+impl ElasticSearchServer {
+    #[tool(description = "Create an index with the given mappings and settings")]
+    async fn create_index(
+        &self,
+        Parameters(CreateIndexRequest {
+            index,
+            mappings,
+            settings,
+        }): Parameters<CreateIndexRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        check_indices_allowed([index.as_str()], &conn_config)?;
+        if index.contains('*') {
+            let error_message = format!("Invalid index name `{index}`: it must not contain `*`");
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+
+        let mut body = Map::new();
+        if let Some(mappings) = mappings {
+            body.insert("mappings".to_string(), mappings);
+        }
+        if let Some(settings) = settings {
+            body.insert("settings".to_string(), settings);
+        }
+
+        let response: CreateIndexResponse = send_request(
+            &conn_config,
+            Method::PUT,
+            &index,
+            Some(Value::Object(body)),
+            "create index",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Index a document. A document with the same id is replaced")]
+    async fn index_document(
+        &self,
+        Parameters(IndexDocumentRequest {
+            id,
+            document,
+            refresh,
+        }): Parameters<IndexDocumentRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let index = &conn_config.index;
+        let (method, path) = match id {
+            Some(id) => (
+                Method::PUT,
+                format!("{index}/_doc/{}", encode_path_segment(&id)),
+            ),
+            None => (Method::POST, format!("{index}/_doc")),
+        };
+
+        let response: WriteDocumentResponse = send_request(
+            &conn_config,
+            method,
+            &format!("{path}{}", refresh_param(refresh)),
+            Some(document),
+            "index document",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Index many documents at once. The documents are given in NDJSON format, one document per line. The result of each document is returned"
+    )]
+    async fn bulk_index(
+        &self,
+        Parameters(BulkIndexRequest {
+            documents,
+            id_field,
+            refresh,
+        }): Parameters<BulkIndexRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let (lines, ndjson) =
+            build_bulk_body(&documents, id_field.as_deref()).map_err(|error_message| {
+                error!("{}", error_message);
+                McpError::invalid_params(error_message, None)
+            })?;
+
+        let index = &conn_config.index;
+        let request = build_request(
+            &conn_config,
+            Method::POST,
+            &format!("{index}/_bulk{}", refresh_param(refresh)),
+        )
+        .header("Content-Type", "application/x-ndjson")
+        .body(ndjson);
+        let response: BulkResult = execute_request(request, "bulk index").await?;
+
+        let items: Vec<BulkItemResult> = response
+            .items
+            .into_iter()
+            .zip(lines)
+            .filter_map(|(item, line)| {
+                let item = item.into_values().next()?;
+                Some(BulkItemResult {
+                    line,
+                    id: item.id,
+                    status: item.status,
+                    result: item.result,
+                    error: item.error.map(|error| error.to_string()),
+                })
+            })
+            .collect();
+        let failed = items.iter().filter(|item| item.error.is_some()).count() as u64;
+
+        let content = Content::json(BulkIndexResponse {
+            took: response.took,
+            indexed: items.len() as u64 - failed,
+            failed,
+            items,
+        })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Update some fields of a document")]
+    async fn update_document(
+        &self,
+        Parameters(UpdateDocumentRequest {
+            id,
+            doc,
+            doc_as_upsert,
+            refresh,
+        }): Parameters<UpdateDocumentRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let body = json!({
+            "doc": doc,
+            "doc_as_upsert": doc_as_upsert.unwrap_or(false),
+        });
+
+        let index = &conn_config.index;
+        let response: WriteDocumentResponse = send_request(
+            &conn_config,
+            Method::POST,
+            &format!(
+                "{index}/_update/{}{}",
+                encode_path_segment(&id),
+                refresh_param(refresh)
+            ),
+            Some(body),
+            "update document",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Delete the documents that match a query")]
+    async fn delete_by_query(
+        &self,
+        Parameters(DeleteByQueryRequest { query, max_docs }): Parameters<DeleteByQueryRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        validate_query_clause(&query).map_err(|error_message| {
+            error!("{}", error_message);
+            McpError::invalid_params(error_message, None)
+        })?;
+
+        let mut body = json!({ "query": query });
+        if let Some(max_docs) = max_docs {
+            body["max_docs"] = json!(max_docs);
+        }
+
+        let index = &conn_config.index;
+        let response: DeleteByQueryResponse = send_request(
+            &conn_config,
+            Method::POST,
+            &format!("{index}/_delete_by_query?refresh=true"),
+            Some(body),
+            "delete by query",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+}
+
+/// Tools that manage index templates, ILM policies, aliases and reindex tasks, e.g. to re-index
+/// with no downtime by swapping an alias. Only enabled with `--enable-admin-tools`. The indices
+/// and aliases that they touch must be allowed.
+#[tool_router(router = admin_tool_router)]
+impl ElasticSearchServer {
+    #[tool(description = "List the index templates, or the templates that match a name pattern")]
+    async fn list_index_templates(
+        &self,
+        Parameters(ListTemplatesRequest { name }): Parameters<ListTemplatesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let templates = get_index_templates(&conn_config, name.as_deref())
+            .await?
+            .into_iter()
+            .map(|entry| IndexTemplate {
+                name: entry.name,
+                index_patterns: entry.index_template.index_patterns,
+                composed_of: entry.index_template.composed_of,
+                priority: entry.index_template.priority,
+                template: entry.index_template.template,
+            })
+            .collect();
+
+        let content = Content::json(ListIndexTemplatesResponse { templates })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Create or replace an index template, which sets the settings, mappings and aliases of the new indices whose name matches its patterns"
+    )]
+    async fn put_index_template(
+        &self,
+        Parameters(PutIndexTemplateRequest {
+            name,
+            index_patterns,
+            composed_of,
+            priority,
+            template,
+        }): Parameters<PutIndexTemplateRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        if index_patterns.is_empty() {
+            let error_message = "At least one index pattern must be given";
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+        check_indices_allowed(index_patterns.iter().map(String::as_str), &conn_config)?;
+
+        let mut body = json!({ "index_patterns": index_patterns });
+        if let Some(composed_of) = composed_of {
+            body["composed_of"] = json!(composed_of);
+        }
+        if let Some(priority) = priority {
+            body["priority"] = json!(priority);
+        }
+        if let Some(template) = template {
+            body["template"] = template;
+        }
+
+        let response: AcknowledgedResponse = send_request(
+            &conn_config,
+            Method::PUT,
+            &format!("_index_template/{}", encode_path_segment(&name)),
+            Some(body),
+            "put index template",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Delete an index template")]
+    async fn delete_index_template(
+        &self,
+        Parameters(DeleteTemplateRequest { name }): Parameters<DeleteTemplateRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        // the name may be a pattern, so all the matching templates are checked
+        for template in get_index_templates(&conn_config, Some(&name)).await? {
+            check_indices_allowed(
+                template
+                    .index_template
+                    .index_patterns
+                    .iter()
+                    .map(String::as_str),
+                &conn_config,
+            )?;
+        }
+
+        let response: AcknowledgedResponse = send_request(
+            &conn_config,
+            Method::DELETE,
+            &format!("_index_template/{}", encode_path_segment(&name)),
+            None,
+            "delete index template",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "List the component templates, or the component templates that match a name pattern"
+    )]
+    async fn list_component_templates(
+        &self,
+        Parameters(ListTemplatesRequest { name }): Parameters<ListTemplatesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let path = match name {
+            Some(name) => format!("_component_template/{}", encode_path_segment(&name)),
+            None => "_component_template".to_string(),
+        };
+        let result: ComponentTemplatesResult = send_request(
+            &conn_config,
+            Method::GET,
+            &path,
+            None,
+            "list component templates",
+        )
+        .await?;
+
+        let templates = result
+            .component_templates
+            .into_iter()
+            .map(|entry| ComponentTemplate {
+                name: entry.name,
+                template: entry.component_template.template,
+            })
+            .collect();
+
+        let content = Content::json(ListComponentTemplatesResponse { templates })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Create or replace a component template, a reusable block of settings, mappings and aliases that index templates are composed of"
+    )]
+    async fn put_component_template(
+        &self,
+        Parameters(PutComponentTemplateRequest { name, template }): Parameters<
+            PutComponentTemplateRequest,
+        >,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        check_component_template_allowed(&name, &conn_config).await?;
+
+        let response: AcknowledgedResponse = send_request(
+            &conn_config,
+            Method::PUT,
+            &format!("_component_template/{}", encode_path_segment(&name)),
+            Some(json!({ "template": template })),
+            "put component template",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Delete a component template that no index template is composed of")]
+    async fn delete_component_template(
+        &self,
+        Parameters(DeleteTemplateRequest { name }): Parameters<DeleteTemplateRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        check_component_template_allowed(&name, &conn_config).await?;
+
+        let response: AcknowledgedResponse = send_request(
+            &conn_config,
+            Method::DELETE,
+            &format!("_component_template/{}", encode_path_segment(&name)),
+            None,
+            "delete component template",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "List the index lifecycle management (ILM) policies with their phases and the indices they manage"
+    )]
+    async fn list_ilm_policies(&self) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let result: Map<String, Value> = send_request(
+            &conn_config,
+            Method::GET,
+            "_ilm/policy",
+            None,
+            "list ILM policies",
+        )
+        .await?;
+
+        let policies = result
+            .into_iter()
+            .map(|(name, policy)| summarize_ilm_policy(name, &policy))
+            .collect();
+
+        let content = Content::json(ListIlmPoliciesResponse { policies })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Attach an index lifecycle management (ILM) policy to an index")]
+    async fn attach_ilm_policy(
+        &self,
+        Parameters(AttachIlmPolicyRequest {
+            index,
+            policy,
+            rollover_alias,
+        }): Parameters<AttachIlmPolicyRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        check_indices_allowed(index.split(','), &conn_config)?;
+        let mut lifecycle = json!({ "name": policy });
+        if let Some(rollover_alias) = rollover_alias {
+            check_indices_allowed([rollover_alias.as_str()], &conn_config)?;
+            lifecycle["rollover_alias"] = json!(rollover_alias);
+        }
+
+        let response: AcknowledgedResponse = send_request(
+            &conn_config,
+            Method::PUT,
+            &format!("{index}/_settings"),
+            Some(json!({ "index": { "lifecycle": lifecycle } })),
+            "attach ILM policy",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Add aliases to indices and remove aliases from indices. All the actions are applied atomically"
+    )]
+    async fn update_aliases(
+        &self,
+        Parameters(UpdateAliasesRequest { actions }): Parameters<UpdateAliasesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        if actions.is_empty() {
+            let error_message = "At least one alias action must be given";
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+
+        let mut alias_actions = Vec::new();
+        for action in actions {
+            let alias_action = match action {
+                AliasAction::Add {
+                    index,
+                    alias,
+                    is_write_index,
+                    filter,
+                } => {
+                    check_indices_allowed([index.as_str(), alias.as_str()], &conn_config)?;
+                    let mut add = json!({ "index": index, "alias": alias });
+                    if let Some(is_write_index) = is_write_index {
+                        add["is_write_index"] = json!(is_write_index);
+                    }
+                    if let Some(filter) = filter {
+                        validate_query_clause(&filter).map_err(|error_message| {
+                            error!("{}", error_message);
+                            McpError::invalid_params(error_message, None)
+                        })?;
+                        add["filter"] = filter;
+                    }
+                    json!({ "add": add })
+                }
+                AliasAction::Remove { index, alias } => {
+                    check_indices_allowed([index.as_str(), alias.as_str()], &conn_config)?;
+                    json!({ "remove": { "index": index, "alias": alias } })
+                }
+            };
+            alias_actions.push(alias_action);
+        }
+
+        let response: AcknowledgedResponse = send_request(
+            &conn_config,
+            Method::POST,
+            "_aliases",
+            Some(json!({ "actions": alias_actions })),
+            "update aliases",
+        )
+        .await?;
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Move an alias to another index atomically, e.g. to switch the searches to a re-indexed copy of an index with no downtime"
+    )]
+    async fn swap_alias(
+        &self,
+        Parameters(SwapAliasRequest {
+            alias,
+            to_index,
+            from_index,
+        }): Parameters<SwapAliasRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        check_indices_allowed([alias.as_str(), to_index.as_str()], &conn_config)?;
+        if alias.contains('*') || to_index.contains('*') {
+            let error_message = "`alias` and `to_index` must be names, not patterns";
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+        let removed_from = match from_index {
+            Some(from_index) => {
+                check_indices_allowed([from_index.as_str()], &conn_config)?;
+                vec![from_index]
+            }
+            None => {
+                // the cat API returns no rows, instead of an error, if the alias does not exist
+                let rows: Vec<CatAliasRow> = send_request(
+                    &conn_config,
+                    Method::GET,
+                    &format!("_cat/aliases/{alias}?format=json"),
                     None,
-                ))
+                    "get alias",
+                )
+                .await?;
+                let removed_from: Vec<String> = rows
+                    .into_iter()
+                    .map(|row| row.index)
+                    .filter(|index| index != &to_index)
+                    .collect();
+                // the alias may also point to indices outside of the allow-list
+                check_indices_allowed(removed_from.iter().map(String::as_str), &conn_config)?;
+                removed_from
+            }
+        };
+
+        let mut actions: Vec<Value> = removed_from
+            .iter()
+            .map(|index| json!({ "remove": { "index": index, "alias": alias } }))
+            .collect();
+        actions.push(json!({ "add": { "index": to_index, "alias": alias } }));
+
+        let _: AcknowledgedResponse = send_request(
+            &conn_config,
+            Method::POST,
+            "_aliases",
+            Some(json!({ "actions": actions })),
+            "swap alias",
+        )
+        .await?;
+
+        let response = SwapAliasResponse {
+            alias,
+            removed_from,
+            added_to: to_index,
+        };
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Start copying the documents of an index into another index, e.g. to apply new mappings. Returns the id of the task, to poll with the `get_task` tool"
+    )]
+    async fn reindex(
+        &self,
+        Parameters(ReindexRequest {
+            source_index,
+            dest_index,
+            query,
+            max_docs,
+        }): Parameters<ReindexRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        check_indices_allowed(source_index.split(','), &conn_config)?;
+        check_indices_allowed([dest_index.as_str()], &conn_config)?;
+        if dest_index.contains('*') {
+            let error_message =
+                format!("Invalid index name `{dest_index}`: it must not contain `*`");
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+
+        let mut body = json!({
+            "source": { "index": source_index },
+            "dest": { "index": dest_index },
+        });
+        if let Some(query) = query {
+            validate_query_clause(&query).map_err(|error_message| {
+                error!("{}", error_message);
+                McpError::invalid_params(error_message, None)
+            })?;
+            body["source"]["query"] = query;
+        }
+        if let Some(max_docs) = max_docs {
+            body["max_docs"] = json!(max_docs);
+        }
+
+        // the task runs in the background, since a reindex usually outlasts a tool call
+        let response: ReindexResponse = send_request(
+            &conn_config,
+            Method::POST,
+            "_reindex?wait_for_completion=false",
+            Some(body),
+            "reindex",
+        )
+        .await?;
+        STARTED_TASKS.lock().unwrap().insert(response.task.clone());
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get the status of a task started by the `reindex` tool: whether it is completed, and the number of documents processed so far"
+    )]
+    async fn get_task(
+        &self,
+        Parameters(TaskRequest { task_id }): Parameters<TaskRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        check_task_started(&task_id)?;
+
+        let result: Value = send_request(
+            &conn_config,
+            Method::GET,
+            &format!("_tasks/{}", encode_path_segment(&task_id)),
+            None,
+            "get task",
+        )
+        .await?;
+
+        let content = Content::json(summarize_task(task_id, &result))?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Cancel a running task started by the `reindex` tool")]
+    async fn cancel_task(
+        &self,
+        Parameters(TaskRequest { task_id }): Parameters<TaskRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        check_task_started(&task_id)?;
+
+        let result: Value = send_request(
+            &conn_config,
+            Method::POST,
+            &format!("_tasks/{}/_cancel", encode_path_segment(&task_id)),
+            None,
+            "cancel task",
+        )
+        .await?;
+
+        let content = Content::json(result)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+}
+
+/// Check that a task was started by the `reindex` tool
+fn check_task_started(task_id: &str) -> Result<(), McpError> {
+    match STARTED_TASKS.lock().unwrap().contains(task_id) {
+        true => Ok(()),
+        false => {
+            let error_message = format!(
+                "Unknown task `{task_id}`: only the tasks started by `reindex` can be used"
+            );
+            error!("{}", error_message);
+            Err(McpError::invalid_params(error_message, None))
+        }
+    }
+}
+
+/// Get the index templates whose name matches a pattern, or all the index templates
+async fn get_index_templates(
+    conn_config: &ConnectionConfig,
+    name: Option<&str>,
+) -> Result<Vec<IndexTemplateEntry>, McpError> {
+    let path = match name {
+        Some(name) => format!("_index_template/{}", encode_path_segment(name)),
+        None => "_index_template".to_string(),
+    };
+    let result: IndexTemplatesResult =
+        send_request(conn_config, Method::GET, &path, None, "get index templates").await?;
+
+    Ok(result.index_templates)
+}
+
+/// Check that a component template can be changed: the index patterns of the index templates
+/// composed of it must be allowed, since a change applies to their new indices too
+async fn check_component_template_allowed(
+    name: &str,
+    conn_config: &ConnectionConfig,
+) -> Result<(), McpError> {
+    if name.contains(['*', ',']) {
+        let error_message =
+            format!("Invalid component template name `{name}`: it must not contain `*` or `,`");
+        error!("{}", error_message);
+        return Err(McpError::invalid_params(error_message, None));
+    }
+
+    for template in get_index_templates(conn_config, None).await? {
+        if template
+            .index_template
+            .composed_of
+            .iter()
+            .any(|c| c == name)
+        {
+            check_indices_allowed(
+                template
+                    .index_template
+                    .index_patterns
+                    .iter()
+                    .map(String::as_str),
+                conn_config,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexTemplatesResult {
+    index_templates: Vec<IndexTemplateEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexTemplateEntry {
+    name: String,
+    index_template: IndexTemplateBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndexTemplateBody {
+    index_patterns: Vec<String>,
+    #[serde(default)]
+    composed_of: Vec<String>,
+    priority: Option<u64>,
+    template: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComponentTemplatesResult {
+    component_templates: Vec<ComponentTemplateEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComponentTemplateEntry {
+    name: String,
+    component_template: ComponentTemplateBody,
+}
+
+#[derive(Debug, Deserialize)]
+struct ComponentTemplateBody {
+    template: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct CatAliasRow {
+    index: String,
+}
+
+/// Summarize an ILM policy: its phases in order, with their actions, and the indices it manages
+fn summarize_ilm_policy(name: String, policy: &Value) -> IlmPolicy {
+    let phases = policy["policy"]["phases"].as_object();
+    let phases = ["hot", "warm", "cold", "frozen", "delete"]
+        .into_iter()
+        .filter_map(|phase_name| {
+            let phase = phases?.get(phase_name)?;
+            Some(IlmPhase {
+                name: phase_name.to_string(),
+                min_age: phase["min_age"].as_str().map(str::to_string),
+                actions: phase["actions"]
+                    .as_object()
+                    .map(|actions| actions.keys().cloned().collect())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect();
+    let indices = policy["in_use_by"]["indices"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|index| index.as_str().map(str::to_string))
+        .collect();
+
+    IlmPolicy {
+        name,
+        phases,
+        indices,
+    }
+}
+
+/// Summarize the status of a task. The counts are those of the reindex, update by query and
+/// delete by query tasks
+fn summarize_task(task_id: String, result: &Value) -> TaskStatusResponse {
+    let task = &result["task"];
+    // the final counts of a completed task are in its response
+    let status = match result.get("response") {
+        Some(response) => response,
+        None => &task["status"],
+    };
+
+    TaskStatusResponse {
+        task_id,
+        completed: result["completed"].as_bool().unwrap_or(false),
+        action: task["action"].as_str().unwrap_or_default().to_string(),
+        description: task["description"].as_str().map(str::to_string),
+        running_time_s: u64_at(task, "/running_time_in_nanos").unwrap_or(0) as f64 / 1e9,
+        total: u64_at(status, "/total").unwrap_or(0),
+        created: u64_at(status, "/created").unwrap_or(0),
+        updated: u64_at(status, "/updated").unwrap_or(0),
+        deleted: u64_at(status, "/deleted").unwrap_or(0),
+        version_conflicts: u64_at(status, "/version_conflicts").unwrap_or(0),
+        failures: status["failures"].as_array().cloned().unwrap_or_default(),
+        error: result.get("error").cloned(),
+    }
+}
+
+/// The `refresh` query parameter of a write request
+fn refresh_param(refresh: Option<bool>) -> &'static str {
+    match refresh {
+        Some(true) => "?refresh=wait_for",
+        _ => "",
+    }
+}
+
+/// Build the NDJSON body of a bulk request from documents in NDJSON format. Returns the line
+/// number of each document with the body.
+fn build_bulk_body(
+    documents: &str,
+    id_field: Option<&str>,
+) -> Result<(Vec<usize>, String), String> {
+    let mut lines = Vec::new();
+    let mut ndjson = String::new();
+
+    for (number, line) in documents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let number = number + 1;
+
+        let document: Map<String, Value> = serde_json::from_str(line)
+            .map_err(|e| format!("Line {number} is not a JSON object: {e}"))?;
+
+        let action = match id_field {
+            Some(id_field) => match document.get(id_field) {
+                Some(Value::String(id)) => json!({ "index": { "_id": id } }),
+                Some(Value::Number(id)) => json!({ "index": { "_id": id.to_string() } }),
+                _ => {
+                    return Err(format!(
+                        "The document of line {number} has no string or number field `{id_field}`"
+                    ));
+                }
+            },
+            None => json!({ "index": {} }),
+        };
+
+        ndjson.push_str(&action.to_string());
+        ndjson.push('\n');
+        ndjson.push_str(&Value::Object(document).to_string());
+        ndjson.push('\n');
+        lines.push(number);
+    }
+
+    match lines.is_empty() {
+        true => Err("No documents to index".to_string()),
+        false => Ok((lines, ndjson)),
+    }
+}
+
+/// The body of a bulk response
+#[derive(Debug, Deserialize)]
+struct BulkResult {
+    took: u64,
+    /// an object per operation, keyed by the action, e.g. `index`
+    items: Vec<HashMap<String, BulkItem>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BulkItem {
+    #[serde(rename = "_id")]
+    id: Option<String>,
+    status: u16,
+    result: Option<String>,
+    error: Option<ErrorCause>,
+}
+
+/// Build the query of the `search` tool: a query text matched against fields
+fn keyword_query(query: &str, fields: &[String], mode: QueryMode) -> Value {
+    let query_type = match mode {
+        QueryMode::MultiMatch => "multi_match",
+        QueryMode::QueryString => "query_string",
+        QueryMode::SimpleQueryString => "simple_query_string",
+    };
+
+    json!({
+        query_type: {
+            "query": query,
+            "fields": fields
+        }
+    })
+}
+
+/// Resolve the fields to search: the given fields, or the fields of the server
+fn resolve_fields(
+    fields: Option<Vec<String>>,
+    conn_config: &ConnectionConfig,
+) -> Result<Vec<String>, McpError> {
+    match fields {
+        Some(fields) => {
+            validate_fields(&fields).map_err(|error_message| {
+                error!("{}", error_message);
+                McpError::invalid_params(error_message, None)
+            })?;
+            Ok(fields)
+        }
+        None => Ok(conn_config.fields.clone()),
+    }
+}
+
+/// A search response with the optional profile of the search
+#[derive(Debug, Deserialize)]
+struct ProfiledSearchResponse {
+    #[serde(flatten)]
+    response: SearchResponse,
+    profile: Option<Value>,
+}
+
+/// Summarize the profile of a search: the time spent by each shard in each phase, and the
+/// slowest Lucene queries
+fn summarize_profile(profile: &Value) -> SearchProfile {
+    let mut shards: Vec<ShardProfile> = profile["shards"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|shard| {
+            let searches = shard["searches"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let mut queries = Vec::new();
+            let (mut query_ms, mut rewrite_ms, mut collector_ms) = (0.0, 0.0, 0.0);
+            for search in searches {
+                for query in search["query"]
+                    .as_array()
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+                {
+                    query_ms += nanos_to_ms(query);
+                    queries.push(summarize_query_profile(query, 1));
+                }
+                rewrite_ms += u64_at(search, "/rewrite_time").unwrap_or(0) as f64 / 1e6;
+                collector_ms += search["collector"]
+                    .as_array()
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+                    .iter()
+                    .map(nanos_to_ms)
+                    .sum::<f64>();
+            }
+            let aggregation_ms = shard["aggregations"]
+                .as_array()
+                .map(Vec::as_slice)
+                .unwrap_or_default()
+                .iter()
+                .map(nanos_to_ms)
+                .sum();
+            let fetch_ms = shard.get("fetch").map(nanos_to_ms);
+
+            ShardProfile {
+                id: shard["id"].as_str().unwrap_or_default().to_string(),
+                query_ms,
+                rewrite_ms,
+                collector_ms,
+                aggregation_ms,
+                fetch_ms,
+                queries,
+            }
+        })
+        .collect();
+
+    shards.sort_by(|a, b| {
+        let total =
+            |shard: &ShardProfile| shard.query_ms + shard.collector_ms + shard.aggregation_ms;
+        total(b).total_cmp(&total(a))
+    });
+
+    SearchProfile { shards }
+}
+
+/// Summarize the profile of a Lucene query and of its children, up to a maximum depth
+fn summarize_query_profile(query: &Value, depth: usize) -> QueryProfile {
+    // the breakdown holds the time of each phase, and the number of calls as `<phase>_count`
+    let mut phases: Vec<(String, f64)> = query["breakdown"]
+        .as_object()
+        .map(|breakdown| {
+            breakdown
+                .iter()
+                .filter(|(name, _)| !name.ends_with("_count"))
+                .filter_map(|(name, nanos)| Some((name.clone(), nanos.as_u64()? as f64 / 1e6)))
+                .filter(|(_, ms)| *ms > 0.0)
+                .collect()
+        })
+        .unwrap_or_default();
+    phases.sort_by(|a, b| b.1.total_cmp(&a.1));
+    phases.truncate(TOP_PROFILED_PHASES);
+
+    let children = match depth < MAX_PROFILED_QUERY_DEPTH {
+        true => query["children"]
+            .as_array()
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .map(|child| summarize_query_profile(child, depth + 1))
+            .collect(),
+        false => Vec::new(),
+    };
+
+    QueryProfile {
+        ty: query["type"].as_str().unwrap_or_default().to_string(),
+        description: query["description"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string(),
+        time_ms: nanos_to_ms(query),
+        top_phases: phases.into_iter().collect(),
+        children,
+    }
+}
+
+/// The `time_in_nanos` of a profiled element, in milliseconds
+fn nanos_to_ms(element: &Value) -> f64 {
+    u64_at(element, "/time_in_nanos").unwrap_or(0) as f64 / 1e6
+}
+
+/// The response of the explain API
+#[derive(Debug, Deserialize)]
+struct ExplainResult {
+    #[serde(rename = "_index")]
+    index: String,
+    #[serde(rename = "_id")]
+    id: String,
+    matched: bool,
+    explanation: Option<Explanation>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Explanation {
+    value: f64,
+    description: String,
+    #[serde(default)]
+    details: Vec<Explanation>,
+}
+
+/// Render the explanation of a score as indented lines, e.g. `2.31 = weight(title:paris)`
+fn render_explanation(explanation: &Explanation, depth: usize, lines: &mut Vec<String>) {
+    lines.push(format!(
+        "{}{} = {}",
+        "  ".repeat(depth),
+        explanation.value,
+        explanation.description
+    ));
+    for detail in &explanation.details {
+        render_explanation(detail, depth + 1, lines);
+    }
+}
+
+/// Render the hits of a search as compact text: the title and the highlighted fragments of each
+/// hit
+fn render_hits_as_text(search_response: &SearchResponse, title_field: &str) -> String {
+    let hits = &search_response.hits.hits;
+    if hits.is_empty() {
+        return "No hits found".to_string();
+    }
+
+    let mut text = String::new();
+    if let Some(total) = &search_response.hits.total {
+        let relation = match total.relation.as_str() {
+            "gte" => "at least ",
+            _ => "",
+        };
+        text.push_str(&format!(
+            "{} of {relation}{} hits\n",
+            hits.len(),
+            total.value
+        ));
+    }
+
+    for (number, hit) in hits.iter().enumerate() {
+        let title = match &hit.source[title_field] {
+            Value::String(title) => title.clone(),
+            Value::Null => hit.id.clone().unwrap_or_default(),
+            title => title.to_string(),
+        };
+        text.push_str(&format!("\n{}. {title}", number + 1));
+
+        let mut details = Vec::new();
+        if let Some(id) = &hit.id {
+            details.push(format!("id: {id}"));
+        }
+        if let Some(score) = hit.score {
+            details.push(format!("score: {score:.3}"));
+        }
+        if !details.is_empty() {
+            text.push_str(&format!(" ({})", details.join(", ")));
+        }
+        text.push('\n');
+
+        if let Some(highlight) = &hit.highlight {
+            // the fragments of the title are redundant with the title
+            let mut fields: Vec<_> = highlight
+                .iter()
+                .filter(|(field, _)| field.as_str() != title_field)
+                .collect();
+            fields.sort_by_key(|(field, _)| field.as_str());
+            for fragment in fields.into_iter().flat_map(|(_, fragments)| fragments) {
+                text.push_str(&format!("   ... {} ...\n", fragment.trim()));
+            }
+        }
+    }
+
+    text
+}
+
+/// Validate a query DSL search request, and build the body of the search request
+fn build_dsl_body(request: DslSearchRequest, default_size: u64) -> Result<Value, String> {
+    let DslSearchRequest {
+        index: _,
+        query,
+        sort,
+        source,
+        from,
+        size,
+        search_after,
+    } = request;
+
+    let mut body = Map::new();
+
+    if let Some(query) = query {
+        validate_query_clause(&query)?;
+        body.insert("query".to_string(), query);
+    }
+
+    if let Some(sort) = sort {
+        if !(sort.is_string() || sort.is_object() || sort.is_array()) {
+            return Err("`sort` must be a field name, an object or a list".to_string());
+        }
+        body.insert("sort".to_string(), sort);
+    }
+
+    if let Some(source) = source {
+        let valid = match &source {
+            Value::Bool(_) | Value::String(_) => true,
+            Value::Array(fields) => fields.iter().all(Value::is_string),
+            Value::Object(filter) => filter.iter().all(|(key, fields)| {
+                matches!(key.as_str(), "includes" | "excludes")
+                    && fields
+                        .as_array()
+                        .is_some_and(|fields| fields.iter().all(Value::is_string))
+            }),
+            _ => false,
+        };
+        if !valid {
+            return Err("`_source` must be a boolean, a list of field patterns, or an object with `includes` and `excludes` lists".to_string());
+        }
+        body.insert("_source".to_string(), source);
+    }
+
+    let size = size.unwrap_or(default_size);
+    let from = from.unwrap_or(0);
+    if from.checked_add(size).is_none_or(|n| n > MAX_RESULT_WINDOW) {
+        return Err(format!(
+            "`from` + `size` must not be greater than {MAX_RESULT_WINDOW}. Use `search_after` to page through more hits"
+        ));
+    }
+    body.insert("size".to_string(), json!(size));
+
+    if let Some(search_after) = search_after {
+        if !body.contains_key("sort") {
+            return Err("`search_after` requires `sort`".to_string());
+        }
+        if from > 0 {
+            return Err("`from` must not be used with `search_after`".to_string());
+        }
+        body.insert("search_after".to_string(), json!(search_after));
+    } else if from > 0 {
+        body.insert("from".to_string(), json!(from));
+    }
+
+    Ok(Value::Object(body))
+}
+
+/// Get the capabilities of the fields of an index. If no field names are given, the capabilities
+/// of all the fields except the metadata fields are returned.
+pub async fn get_field_caps(
+    conn_config: &ConnectionConfig,
+    index: &str,
+    fields: Option<&[String]>,
+) -> Result<Vec<FieldCapability>, McpError> {
+    let pattern = match fields {
+        Some(fields) if !fields.is_empty() => fields.join(","),
+        _ => "*".to_string(),
+    };
+
+    let request = build_request(conn_config, Method::GET, &format!("{index}/_field_caps"))
+        .query(&[("fields", &pattern)]);
+    let response: FieldCapsResult = execute_request(request, "get field capabilities").await?;
+
+    let mut capabilities = Vec::new();
+    for (name, types) in response.fields {
+        // metadata fields, e.g. `_id`, are only returned if asked for by name
+        if pattern == "*" && name.starts_with('_') {
+            continue;
+        }
+        for (ty, capability) in types {
+            capabilities.push(FieldCapability {
+                name: name.clone(),
+                ty,
+                searchable: capability.searchable,
+                aggregatable: capability.aggregatable,
+                indices: capability.indices,
+            });
+        }
+    }
+    capabilities.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(capabilities)
+}
+
+/// The body of a field capabilities response
+#[derive(Debug, Deserialize)]
+struct FieldCapsResult {
+    fields: HashMap<String, HashMap<String, FieldCapsEntry>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FieldCapsEntry {
+    searchable: bool,
+    aggregatable: bool,
+    indices: Option<Vec<String>>,
+}
+
+/// Flatten the `properties` of a mapping into a list of fields, including the sub-fields of
+/// objects and the multi-fields
+fn flatten_properties(
+    properties: &Map<String, Value>,
+    prefix: &str,
+    fields: &mut Vec<FieldMapping>,
+) {
+    for (name, property) in properties {
+        let name = format!("{prefix}{name}");
+
+        fields.push(FieldMapping {
+            name: name.clone(),
+            ty: property["type"].as_str().unwrap_or("object").to_string(),
+            analyzer: property["analyzer"].as_str().map(str::to_string),
+        });
+
+        if let Some(properties) = property["properties"].as_object() {
+            flatten_properties(properties, &format!("{name}."), fields);
+        }
+        if let Some(multi_fields) = property["fields"].as_object() {
+            flatten_properties(multi_fields, &format!("{name}."), fields);
+        }
+    }
+}
+
+/// Compute the embedding of a query with the embedding service
+async fn compute_embedding(
+    semantic_config: &SemanticSearchConfig,
+    query: &str,
+) -> Result<Vec<f64>, McpError> {
+    let embedding_service = &semantic_config.embedding_service;
+    let embedding_service_url =
+        format!("{}/embeddings", embedding_service.url.trim_end_matches('/'));
+
+    // create a embedding request
+    let embedding_request = EmbeddingRequest {
+        model: semantic_config.embedding_model.clone(),
+        input: InputText::String(query.to_string()),
+        encoding_format: None,
+        user: None,
+    };
+
+    let mut request = reqwest::Client::new()
+        .post(&embedding_service_url)
+        .header("Content-Type", "application/json");
+    if let Some(api_key) = &embedding_service.api_key {
+        request = request.header("Authorization", api_key);
+    }
+
+    let response = request.json(&embedding_request).send().await.map_err(|e| {
+        let error_message = format!("Failed to send the embedding request: {e}");
+        error!("{}", error_message);
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })?;
+
+    let status = response.status();
+    let bytes = response.bytes().await.map_err(|e| {
+        let error_message = format!("Failed to parse embeddings response: {e}");
+        error!("{}", error_message);
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })?;
+    if !status.is_success() {
+        let error_message = format!(
+            "Failed to compute the embedding of the query: {status}. {}",
+            String::from_utf8_lossy(&bytes)
+        );
+        error!("{}", error_message);
+        return Err(McpError::new(
+            ErrorCode::INTERNAL_ERROR,
+            error_message,
+            None,
+        ));
+    }
+
+    // parse the response
+    let embedding_response = serde_json::from_slice::<EmbeddingsResponse>(&bytes).map_err(|e| {
+        let error_message = format!("Failed to parse embeddings response: {e}");
+        error!("{}", error_message);
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })?;
+
+    match embedding_response.data.into_iter().next() {
+        Some(embedding) => Ok(embedding.embedding),
+        None => {
+            let error_message = "No embeddings returned";
+            error!("{}", error_message);
+            Err(McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                error_message,
+                None,
+            ))
+        }
+    }
+}
+
+/// The body of an ES|QL query response
+#[derive(Debug, Deserialize)]
+struct EsqlResult {
+    columns: Vec<Column>,
+    values: Vec<Vec<Value>>,
+}
+
+/// The body of a SQL query response
+#[derive(Debug, Deserialize)]
+struct SqlResult {
+    /// only returned with the first page
+    #[serde(default)]
+    columns: Vec<Column>,
+    rows: Vec<Vec<Value>>,
+    /// returned if there are more pages
+    cursor: Option<String>,
+}
+
+/// Check the row limit of an ES|QL or SQL query, and return the limit to use
+fn query_row_limit(limit: Option<u64>) -> Result<u64, McpError> {
+    match limit.unwrap_or(DEFAULT_QUERY_ROWS) {
+        limit @ 1..=MAX_QUERY_ROWS => Ok(limit),
+        limit => {
+            let error_message =
+                format!("`limit` must be between 1 and {MAX_QUERY_ROWS}, not {limit}");
+            error!("{}", error_message);
+            Err(McpError::invalid_params(error_message, None))
+        }
+    }
+}
+
+/// The first keyword of a query, in upper case
+fn first_keyword(query: &str) -> String {
+    query
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_uppercase()
+}
+
+/// Build the response of an ES|QL or SQL query, keeping at most `limit` rows
+fn tabular_response(
+    columns: Vec<Column>,
+    mut rows: Vec<Vec<Value>>,
+    limit: u64,
+) -> TabularQueryResponse {
+    let truncated = rows.len() as u64 > limit;
+    rows.truncate(limit as usize);
+
+    TabularQueryResponse {
+        columns,
+        rows,
+        truncated,
+    }
+}
+
+/// The number at a JSON pointer, e.g. `/indices/docs/count`
+fn u64_at(value: &Value, pointer: &str) -> Option<u64> {
+    value.pointer(pointer).and_then(Value::as_u64)
+}
+
+/// Summarize the statistics of a node returned by the nodes stats API
+fn summarize_node_stats(node: &Value) -> NodeStats {
+    let roles = node["roles"]
+        .as_array()
+        .map(|roles| {
+            roles
+                .iter()
+                .filter_map(|role| role.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    NodeStats {
+        name: node["name"].as_str().unwrap_or_default().to_string(),
+        host: node["host"].as_str().map(str::to_string),
+        roles,
+        cpu_percent: u64_at(node, "/os/cpu/percent"),
+        load_average_1m: node
+            .pointer("/os/cpu/load_average/1m")
+            .and_then(Value::as_f64),
+        heap_used_percent: u64_at(node, "/jvm/mem/heap_used_percent"),
+        disk_total_in_bytes: u64_at(node, "/fs/total/total_in_bytes"),
+        disk_available_in_bytes: u64_at(node, "/fs/total/available_in_bytes"),
+        docs_count: u64_at(node, "/indices/docs/count"),
+        store_size_in_bytes: u64_at(node, "/indices/store/size_in_bytes"),
+        search_query_total: u64_at(node, "/indices/search/query_total"),
+        search_query_time_in_millis: u64_at(node, "/indices/search/query_time_in_millis"),
+        indexing_index_total: u64_at(node, "/indices/indexing/index_total"),
+        indexing_index_time_in_millis: u64_at(node, "/indices/indexing/index_time_in_millis"),
+    }
+}
+
+/// Summarize the response of the cluster allocation explain API
+fn summarize_allocation(explanation: &Value) -> AllocationExplanation {
+    // the reasons of the nodes are often the same, so they are deduplicated
+    let mut decider_explanations: Vec<String> = Vec::new();
+    let decisions = explanation["node_allocation_decisions"]
+        .as_array()
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for decider in decisions
+        .iter()
+        .filter_map(|decision| decision["deciders"].as_array())
+        .flatten()
+        .filter(|decider| decider["decision"] == "NO")
+    {
+        let reason = format!(
+            "{}: {}",
+            decider["decider"].as_str().unwrap_or_default(),
+            decider["explanation"].as_str().unwrap_or_default()
+        );
+        if !decider_explanations.contains(&reason) {
+            decider_explanations.push(reason);
+        }
+    }
+
+    AllocationExplanation {
+        can_allocate: explanation["can_allocate"].as_str().map(str::to_string),
+        allocate_explanation: explanation["allocate_explanation"]
+            .as_str()
+            .map(str::to_string),
+        decider_explanations,
+    }
+}
+
+/// The `_source` filter of a request, if any fields are included or excluded
+fn source_filter(includes: Option<Vec<String>>, excludes: Option<Vec<String>>) -> Option<Value> {
+    match (includes, excludes) {
+        (None, None) => None,
+        (includes, excludes) => Some(json!({
+            "includes": includes.unwrap_or_default(),
+            "excludes": excludes.unwrap_or_default(),
+        })),
+    }
+}
+
+/// Get documents by their ids. A document that does not exist is returned with `found: false`.
+async fn multi_get(
+    conn_config: &ConnectionConfig,
+    index: &str,
+    ids: &[String],
+    source: Option<Value>,
+    operation: &str,
+) -> Result<Vec<Document>, McpError> {
+    let docs: Vec<Value> = ids
+        .iter()
+        .map(|id| {
+            let mut doc = json!({ "_id": id });
+            if let Some(source) = &source {
+                doc["_source"] = source.clone();
+            }
+            doc
+        })
+        .collect();
+
+    let response: MgetResponse = send_request(
+        conn_config,
+        Method::POST,
+        &format!("{index}/_mget"),
+        Some(json!({ "docs": docs })),
+        operation,
+    )
+    .await?;
+
+    Ok(response.docs)
+}
+
+/// The state of a point-in-time search, kept on the server between pages. The client only gets
+/// the random id of the state as an opaque cursor
+#[derive(Debug)]
+struct PageCursor {
+    pit_id: String,
+    /// the sort values of the last hit of the previous page
+    search_after: Option<Vec<Value>>,
+    query: Option<Value>,
+    sort: Option<Value>,
+    source: Option<Value>,
+    size: u64,
+}
+impl PageCursor {
+    /// Keep the state of a cursor for the next page, and return the id of the cursor
+    fn store(self) -> String {
+        let id = URL_SAFE_NO_PAD.encode(rand::random::<[u8; 16]>());
+
+        let mut cursors = PAGE_CURSORS.lock().unwrap();
+        // the expired cursors point to closed points-in-time
+        cursors.retain(|_, (created, _)| created.elapsed() < CURSOR_KEEP_ALIVE);
+        cursors.insert(id.clone(), (Instant::now(), self));
+
+        id
+    }
+
+    /// Take the state of a cursor given by a client. A cursor can only be used once, the next
+    /// page coming with a new cursor
+    fn take(id: &str) -> Result<Self, McpError> {
+        PAGE_CURSORS
+            .lock()
+            .unwrap()
+            .remove(id.trim())
+            .filter(|(created, _)| created.elapsed() < CURSOR_KEEP_ALIVE)
+            .map(|(_, cursor)| cursor)
+            .ok_or_else(|| {
+                let error_message = "Invalid or expired cursor. Pass the `cursor` returned by the previous page, or no cursor to start a new search";
+                error!("{}", error_message);
+                McpError::invalid_params(error_message, None)
+            })
+    }
+}
+
+fn validate_page_size(size: u64) -> Result<(), McpError> {
+    match size == 0 || size > MAX_PAGE_SIZE {
+        true => {
+            let error_message = format!("`size` must be between 1 and {MAX_PAGE_SIZE}");
+            error!("{}", error_message);
+            Err(McpError::invalid_params(error_message, None))
+        }
+        false => Ok(()),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenPitResponse {
+    id: String,
+}
+
+/// The part of the response of a point-in-time search used to get the next page
+#[derive(Debug, Deserialize)]
+struct PitSearchResponse {
+    /// the id of the point-in-time, which may change between pages
+    pit_id: String,
+    hits: Hits,
+}
+
+/// Open a point-in-time of an index, and return its id
+async fn open_pit(conn_config: &ConnectionConfig, index: &str) -> Result<String, McpError> {
+    let response: OpenPitResponse = send_request(
+        conn_config,
+        Method::POST,
+        &format!("{index}/_pit?keep_alive={PIT_KEEP_ALIVE}"),
+        None,
+        "open a point-in-time",
+    )
+    .await?;
+
+    Ok(response.id)
+}
+
+/// Close a point-in-time. A failure is only logged, since the point-in-time expires anyway
+async fn close_pit(conn_config: &ConnectionConfig, pit_id: &str) {
+    let result: Result<Value, McpError> = send_request(
+        conn_config,
+        Method::DELETE,
+        "_pit",
+        Some(json!({ "id": pit_id })),
+        "close the point-in-time",
+    )
+    .await;
+    if let Err(e) = result {
+        tracing::warn!("{}", e.message);
+    }
+}
+
+/// Get the page of a point-in-time search that follows the `search_after` values of a cursor
+async fn search_pit(
+    conn_config: &ConnectionConfig,
+    cursor: &PageCursor,
+) -> Result<PitSearchResponse, McpError> {
+    let mut body = json!({
+        "pit": {
+            "id": cursor.pit_id,
+            "keep_alive": PIT_KEEP_ALIVE,
+        },
+        "size": cursor.size,
+        "sort": cursor.sort,
+    });
+    if let Some(query) = &cursor.query {
+        body["query"] = query.clone();
+    }
+    if let Some(source) = &cursor.source {
+        body["_source"] = source.clone();
+    }
+    if let Some(search_after) = &cursor.search_after {
+        body["search_after"] = json!(search_after);
+    }
+
+    // the index of a point-in-time search is given by the point-in-time
+    send_request(conn_config, Method::POST, "_search", Some(body), "search").await
+}
+
+/// Render hits as NDJSON, one `{"_index", "_id", "_source"}` object per line
+fn render_hits_as_ndjson(hits: &[Hit]) -> String {
+    let mut ndjson = String::new();
+    for hit in hits {
+        let line = json!({
+            "_index": hit.index,
+            "_id": hit.id,
+            "_source": hit.source,
+        });
+        ndjson.push_str(&line.to_string());
+        ndjson.push('\n');
+    }
+    ndjson
+}
+
+/// Render hits as CSV. The columns are `_id`, the given fields, and the other fields of the
+/// sources. The fields of objects are flattened, e.g. `author.name`, and arrays are written as
+/// JSON.
+fn render_hits_as_csv(hits: &[Hit], fields: Option<&[String]>) -> String {
+    let rows: Vec<Map<String, Value>> = hits
+        .iter()
+        .map(|hit| {
+            let mut row = Map::new();
+            flatten_source("", &hit.source, &mut row);
+            row
+        })
+        .collect();
+
+    // the fields given without wildcards keep their order
+    let mut columns: Vec<String> = fields
+        .unwrap_or_default()
+        .iter()
+        .filter(|field| !field.contains('*'))
+        .cloned()
+        .collect();
+    for row in &rows {
+        for key in row.keys() {
+            if !columns.contains(key) {
+                columns.push(key.clone());
+            }
+        }
+    }
+
+    let mut csv = String::new();
+    let header: Vec<String> = std::iter::once("_id")
+        .chain(columns.iter().map(String::as_str))
+        .map(escape_csv)
+        .collect();
+    csv.push_str(&header.join(","));
+    csv.push('\n');
+
+    for (hit, row) in hits.iter().zip(&rows) {
+        let mut cells = vec![escape_csv(hit.id.as_deref().unwrap_or_default())];
+        for column in &columns {
+            let cell = match row.get(column) {
+                None | Some(Value::Null) => String::new(),
+                Some(Value::String(value)) => escape_csv(value),
+                Some(value) => escape_csv(&value.to_string()),
+            };
+            cells.push(cell);
+        }
+        csv.push_str(&cells.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Flatten the fields of a source into dotted names, e.g. `{"author": {"name": ..}}` into
+/// `author.name`
+fn flatten_source(prefix: &str, value: &Value, row: &mut Map<String, Value>) {
+    match value {
+        Value::Object(object) => {
+            for (key, value) in object {
+                let name = match prefix.is_empty() {
+                    true => key.clone(),
+                    false => format!("{prefix}.{key}"),
+                };
+                flatten_source(&name, value, row);
+            }
+        }
+        value if !prefix.is_empty() => {
+            row.insert(prefix.to_string(), value.clone());
+        }
+        _ => {}
+    }
+}
+
+/// Quote a CSV value if it contains a comma, a quote or a line break
+fn escape_csv(value: &str) -> String {
+    match value.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", value.replace('"', "\"\"")),
+        false => value.to_string(),
+    }
+}
+
+/// Resolve the index of a request: the given index, or the default index of the server. Each
+/// index, alias or pattern of a comma-separated list must be allowed.
+fn resolve_index(
+    index: Option<String>,
+    conn_config: &ConnectionConfig,
+) -> Result<String, McpError> {
+    match index {
+        Some(index) => {
+            check_indices_allowed(index.split(','), conn_config)?;
+            Ok(index)
+        }
+        None => Ok(conn_config.index.clone()),
+    }
+}
+
+/// Check that indices, aliases or patterns are allowed. A pattern is allowed if an allowed
+/// pattern matches it, e.g. `logs-2025*` is allowed by `logs-*`.
+fn check_indices_allowed<'a>(
+    indices: impl IntoIterator<Item = &'a str>,
+    conn_config: &ConnectionConfig,
+) -> Result<(), McpError> {
+    for index in indices {
+        let index = index.trim();
+        validate_index_name(index)?;
+
+        let allowed = conn_config
+            .allowed_indices
+            .iter()
+            .any(|pattern| matches_pattern(pattern, index));
+        if !allowed {
+            let error_message = format!(
+                "The index `{index}` is not allowed. Allowed indices: {}",
+                conn_config.allowed_indices.join(", ")
+            );
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that an index name has none of the characters that would change the path of a request
+fn validate_index_name(index: &str) -> Result<(), McpError> {
+    let invalid = index.is_empty()
+        || index
+            .chars()
+            .any(|c| c.is_whitespace() || "/\\?#%\"<>|,".contains(c));
+    match invalid {
+        true => {
+            let error_message = format!(
+                "Invalid index name `{index}`: it must not be empty nor contain whitespaces or any of `/\\?#%\"<>|,`"
+            );
+            error!("{}", error_message);
+            Err(McpError::invalid_params(error_message, None))
+        }
+        false => Ok(()),
+    }
+}
+
+/// Percent-encode a value used as a segment of the path of a request, e.g. a document id
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::new();
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Check if a name matches a pattern that may contain `*` wildcards
+pub fn matches_pattern(pattern: &str, name: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // no wildcard
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(position) => rest = &rest[position + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// The indices read by an ES|QL query: the indices of the `FROM` source command, and of the
+/// `LOOKUP JOIN` commands
+fn esql_indices(query: &str) -> Vec<&str> {
+    let mut indices = Vec::new();
+
+    for (position, command) in query.split('|').enumerate() {
+        let command = command.trim();
+        let keyword = first_keyword(command);
+        if position == 0 && keyword == "FROM" {
+            let sources = command[keyword.len()..].trim_start();
+            // the metadata fields are not indices, e.g. `FROM logs-* METADATA _id`
+            let sources = match sources.to_ascii_uppercase().find(" METADATA") {
+                Some(end) => &sources[..end],
+                None => sources,
+            };
+            indices.extend(
+                sources
+                    .split(',')
+                    .map(|index| index.trim().trim_matches('"')),
+            );
+        } else if keyword == "LOOKUP" {
+            let mut words = command.split_whitespace().skip(1);
+            if let (Some(_join), Some(index)) = (words.next(), words.next()) {
+                indices.push(index.trim_matches('"'));
+            }
+        }
+    }
+
+    indices
+}
+
+/// The indices read by a SQL query: the tables that follow the `FROM` keywords
+fn sql_indices(query: &str) -> Vec<&str> {
+    let mut indices = Vec::new();
+    let upper = query.to_ascii_uppercase();
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    for (start, _) in upper.match_indices("FROM") {
+        let end = start + "FROM".len();
+        let before = query[..start].chars().next_back();
+        let after = query[end..].chars().next();
+        if before.is_some_and(is_word_char) || after.is_some_and(is_word_char) {
+            continue;
+        }
+
+        let rest = query[end..].trim_start();
+        let table = match rest.chars().next() {
+            // the tables of a subquery follow its own `FROM` keyword
+            Some('(') => continue,
+            Some(quote @ ('"' | '`')) => rest[1..].split(quote).next().unwrap_or_default(),
+            _ => rest
+                .split(|c: char| c.is_whitespace() || c == ')' || c == ';')
+                .next()
+                .unwrap_or_default(),
+        };
+        indices.extend(table.split(','));
+    }
+
+    indices
+}
+
+/// Check that fields have a valid name and an optional numeric boost, e.g. `title^3`
+fn validate_fields(fields: &[String]) -> Result<(), String> {
+    if fields.is_empty() {
+        return Err("At least one field must be given".to_string());
+    }
+    for field in fields {
+        let (name, boost) = match field.split_once('^') {
+            Some((name, boost)) => (name, Some(boost)),
+            None => (field.as_str(), None),
+        };
+        if name.is_empty() || boost.is_some_and(|boost| boost.parse::<f64>().is_err()) {
+            return Err(format!(
+                "Invalid field `{field}`: it must be a field name with an optional numeric boost, e.g. `title^3`"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that a query is an object with a single query clause
+fn validate_query_clause(query: &Value) -> Result<(), String> {
+    match query.as_object() {
+        Some(clause) if clause.len() == 1 => Ok(()),
+        _ => Err(
+            "`query` must be an object with a single query clause, e.g. `{\"match\": {\"title\": \"paris\"}}`"
+                .to_string(),
+        ),
+    }
+}
+
+/// The part of a search response that holds the results of aggregations
+#[derive(Debug, Deserialize)]
+struct AggregationSearchResponse {
+    took: u64,
+    hits: AggregationHits,
+    #[serde(default)]
+    aggregations: Map<String, Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AggregationHits {
+    total: Option<TotalHits>,
+}
+
+/// Validate aggregations, and build the `aggs` object of a search request
+fn build_aggregations(aggregations: &[Aggregation]) -> Result<Value, String> {
+    let mut aggs = Map::new();
+
+    for Aggregation {
+        name,
+        kind,
+        sub_aggregations,
+    } in aggregations
+    {
+        if name.is_empty() || name.contains(['[', ']', '>', '.']) {
+            return Err(format!(
+                "Invalid aggregation name `{name}`: it must not be empty nor contain `[`, `]`, `>` or `.`"
+            ));
+        }
+        if aggs.contains_key(name) {
+            return Err(format!("Duplicate aggregation name `{name}`"));
+        }
+
+        let mut aggregation = match kind {
+            AggregationKind::Terms { field, size } => {
+                let mut terms = json!({ "field": field });
+                if let Some(size) = size {
+                    terms["size"] = json!(size);
+                }
+                json!({ "terms": terms })
+            }
+            AggregationKind::DateHistogram {
+                field,
+                calendar_interval,
+                fixed_interval,
+                format,
+                time_zone,
+            } => {
+                let mut date_histogram = json!({ "field": field });
+                match (calendar_interval, fixed_interval) {
+                    (Some(interval), None) => date_histogram["calendar_interval"] = json!(interval),
+                    (None, Some(interval)) => date_histogram["fixed_interval"] = json!(interval),
+                    _ => {
+                        return Err(format!(
+                            "The date histogram `{name}` requires either `calendar_interval` or `fixed_interval`"
+                        ));
+                    }
+                }
+                if let Some(format) = format {
+                    date_histogram["format"] = json!(format);
+                }
+                if let Some(time_zone) = time_zone {
+                    date_histogram["time_zone"] = json!(time_zone);
+                }
+                json!({ "date_histogram": date_histogram })
+            }
+            AggregationKind::Histogram { field, interval } => {
+                json!({ "histogram": { "field": field, "interval": interval } })
+            }
+            AggregationKind::Stats { field } => json!({ "stats": { "field": field } }),
+            AggregationKind::Cardinality { field } => {
+                json!({ "cardinality": { "field": field } })
+            }
+        };
+
+        if !sub_aggregations.is_empty() {
+            if !is_bucket_aggregation(kind) {
+                return Err(format!(
+                    "The metric aggregation `{name}` can not have sub-aggregations"
+                ));
+            }
+            aggregation["aggs"] = build_aggregations(sub_aggregations)?;
+        }
+
+        aggs.insert(name.clone(), aggregation);
+    }
+
+    Ok(Value::Object(aggs))
+}
+
+fn is_bucket_aggregation(kind: &AggregationKind) -> bool {
+    matches!(
+        kind,
+        AggregationKind::Terms { .. }
+            | AggregationKind::DateHistogram { .. }
+            | AggregationKind::Histogram { .. }
+    )
+}
+
+/// Flatten the results of aggregations into rows. `results` is the object that holds the results
+/// of the aggregations, and `row` holds the keys of the parent buckets. A row is pushed for each
+/// bucket that has no bucket sub-aggregations.
+fn flatten_aggregations(
+    aggregations: &[Aggregation],
+    results: &Map<String, Value>,
+    mut row: Map<String, Value>,
+    rows: &mut Vec<Map<String, Value>>,
+) {
+    // the values of the metric aggregations are added to the rows of the buckets
+    for Aggregation { name, kind, .. } in aggregations {
+        let Some(result) = results.get(name) else {
+            continue;
+        };
+        match kind {
+            AggregationKind::Stats { .. } => {
+                for stat in ["count", "min", "max", "avg", "sum"] {
+                    row.insert(format!("{name}.{stat}"), result[stat].clone());
+                }
             }
+            AggregationKind::Cardinality { .. } => {
+                row.insert(name.clone(), result["value"].clone());
+            }
+            _ => {}
+        }
+    }
+
+    let mut has_buckets = false;
+    for Aggregation {
+        name,
+        kind,
+        sub_aggregations,
+    } in aggregations
+    {
+        if !is_bucket_aggregation(kind) {
+            continue;
+        }
+        has_buckets = true;
+
+        let buckets = results
+            .get(name)
+            .and_then(|result| result["buckets"].as_array())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        for bucket in buckets {
+            let Some(bucket) = bucket.as_object() else {
+                continue;
+            };
+
+            // dates are more readable as formatted strings than as epoch milliseconds
+            let key = bucket
+                .get("key_as_string")
+                .or_else(|| bucket.get("key"))
+                .cloned()
+                .unwrap_or_default();
+
+            let mut bucket_row = row.clone();
+            bucket_row.insert(name.clone(), key);
+            bucket_row.insert(
+                format!("{name}.doc_count"),
+                bucket.get("doc_count").cloned().unwrap_or_default(),
+            );
+
+            flatten_aggregations(sub_aggregations, bucket, bucket_row, rows);
+        }
+    }
+
+    if !has_buckets {
+        rows.push(row);
+    }
+}
+
+async fn get_connection_config() -> Result<ConnectionConfig, McpError> {
+    match CONNECTION_CONFIG.get() {
+        Some(connection_config) => {
+            let conn_config = connection_config.read().await;
+            Ok(conn_config.clone())
+        }
+        None => {
+            let error_message = "Connection config not found";
+            error!("{}", error_message);
+            Err(McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                error_message,
+                None,
+            ))
         }
     }
 }
 
+/// Send a request to the Elasticsearch server, and return the parsed response body
+async fn send_request<T: DeserializeOwned>(
+    conn_config: &ConnectionConfig,
+    method: Method,
+    path: &str,
+    body: Option<Value>,
+    operation: &str,
+) -> Result<T, McpError> {
+    let mut request = build_request(conn_config, method, path);
+    if let Some(body) = body {
+        request = request
+            .header("Content-Type", "application/json")
+            .json(&body);
+    }
+
+    execute_request(request, operation).await
+}
+
+/// Build a request to the Elasticsearch server with the credentials of the connection config
+fn build_request(
+    conn_config: &ConnectionConfig,
+    method: Method,
+    path: &str,
+) -> reqwest::RequestBuilder {
+    // build url
+    let base_url = conn_config.base_url.trim_end_matches('/');
+    let url = format!("{base_url}/{path}");
+
+    let request = conn_config.client.request(method, &url);
+    match &conn_config.auth {
+        Auth::None => request,
+        Auth::Basic { username, password } => request.basic_auth(username, Some(password)),
+        Auth::ApiKey(api_key) => request.header("Authorization", format!("ApiKey {api_key}")),
+        Auth::Bearer(token) => request.bearer_auth(token),
+    }
+}
+
+/// Send a request, and return the parsed response body
+async fn execute_request<T: DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    operation: &str,
+) -> Result<T, McpError> {
+    let response = request.send().await.map_err(|e| {
+        let error_message = format!("Failed to {operation}: {e}");
+        error!("{}", error_message);
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })?;
+
+    let status = response.status();
+    let body = response.bytes().await.map_err(|e| {
+        let error_message = format!("Failed to {operation}: {e}");
+        error!("{}", error_message);
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })?;
+
+    let error_message = match status.is_success() {
+        true => match serde_json::from_slice::<T>(&body) {
+            Ok(result) => return Ok(result),
+            Err(e) => format!("Failed to parse the response to {operation}: {e}"),
+        },
+        false => match serde_json::from_slice::<ErrorResponse>(&body) {
+            Ok(ErrorResponse { error }) => format!("Failed to {operation}: {status}. {error}"),
+            Err(_) => format!("Failed to {operation}: {status}"),
+        },
+    };
+
+    error!("{}", error_message);
+
+    // a bad request is caused by the arguments of the tool, e.g. a malformed query
+    match status == reqwest::StatusCode::BAD_REQUEST {
+        true => Err(McpError::invalid_params(error_message, None)),
+        false => Err(McpError::new(
+            ErrorCode::INTERNAL_ERROR,
+            error_message,
+            None,
+        )),
+    }
+}
+
 #[tool_handler]
 impl ServerHandler for ElasticSearchServer {
     fn get_info(&self) -> ServerInfo {
@@ -357,3 +3454,302 @@ impl ServerHandler for ElasticSearchServer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conn_config(allowed_indices: &[&str]) -> ConnectionConfig {
+        ConnectionConfig {
+            base_url: "http://localhost:9200".to_string(),
+            auth: Auth::None,
+            client: reqwest::Client::new(),
+            index: "products".to_string(),
+            allowed_indices: allowed_indices
+                .iter()
+                .map(|index| index.to_string())
+                .collect(),
+            fields: vec!["title".to_string()],
+            size: 10,
+            title_field: "title".to_string(),
+            max_export_rows: 10000,
+            semantic_search: None,
+        }
+    }
+
+    fn hit(id: &str, source: Value) -> Hit {
+        serde_json::from_value(
+            json!({ "_index": "products", "_id": id, "_score": 1.0, "_source": source }),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn match_patterns() {
+        assert!(matches_pattern("products", "products"));
+        assert!(!matches_pattern("products", "products-2025"));
+        assert!(!matches_pattern("products", "product"));
+        assert!(matches_pattern("logs-*", "logs-"));
+        assert!(matches_pattern("logs-*", "logs-2025.01"));
+        assert!(!matches_pattern("logs-*", "metrics-2025"));
+        assert!(matches_pattern("*-prod", "orders-prod"));
+        assert!(!matches_pattern("*-prod", "orders-prod-old"));
+        assert!(matches_pattern("logs-*-prod", "logs-web-prod"));
+        assert!(!matches_pattern("logs-*-prod", "logs-prod"));
+        assert!(matches_pattern("*", "anything"));
+        // the allowed pattern must cover the requested pattern
+        assert!(matches_pattern("logs-*", "logs-2025*"));
+        assert!(!matches_pattern("logs-2025*", "logs-*"));
+    }
+
+    #[test]
+    fn check_allowed_indices() {
+        let conn_config = conn_config(&["products", "logs-*"]);
+
+        assert!(check_indices_allowed(["products"], &conn_config).is_ok());
+        assert!(check_indices_allowed(["logs-web", " logs-2025*"], &conn_config).is_ok());
+        assert!(check_indices_allowed(["products", "orders"], &conn_config).is_err());
+        assert!(check_indices_allowed(["*"], &conn_config).is_err());
+        assert!(check_indices_allowed(["logs-web/_doc"], &conn_config).is_err());
+        assert!(check_indices_allowed([""], &conn_config).is_err());
+    }
+
+    #[test]
+    fn resolve_comma_separated_indices() {
+        let conn_config = conn_config(&["products", "logs-*"]);
+
+        assert_eq!(resolve_index(None, &conn_config).unwrap(), "products");
+        assert_eq!(
+            resolve_index(Some("products,logs-web".to_string()), &conn_config).unwrap(),
+            "products,logs-web"
+        );
+        assert!(resolve_index(Some("products,orders".to_string()), &conn_config).is_err());
+    }
+
+    #[test]
+    fn validate_index_names() {
+        assert!(validate_index_name("logs-2025.01").is_ok());
+        assert!(validate_index_name("logs-*").is_ok());
+        for name in [
+            "",
+            "logs web",
+            "logs/_doc",
+            "..\\logs",
+            "logs?pretty",
+            "logs#",
+            "logs%2F",
+            "\"logs\"",
+            "<logs>",
+            "logs|x",
+            "logs,orders",
+        ] {
+            assert!(validate_index_name(name).is_err(), "{name}");
+        }
+    }
+
+    #[test]
+    fn extract_esql_indices() {
+        assert_eq!(esql_indices("FROM products | LIMIT 10"), ["products"]);
+        assert_eq!(
+            esql_indices("from logs-*, \"orders\" METADATA _id | KEEP _id"),
+            ["logs-*", "orders"]
+        );
+        assert_eq!(
+            esql_indices("FROM logs | LOOKUP JOIN hosts ON host.name | LIMIT 5"),
+            ["logs", "hosts"]
+        );
+        assert!(esql_indices("ROW a = 1").is_empty());
+    }
+
+    #[test]
+    fn extract_sql_indices() {
+        assert_eq!(sql_indices("SELECT * FROM products LIMIT 10"), ["products"]);
+        assert_eq!(sql_indices("select title from \"logs-*\""), ["logs-*"]);
+        assert_eq!(
+            sql_indices("SELECT * FROM `logs-2025.01`;"),
+            ["logs-2025.01"]
+        );
+        assert_eq!(
+            sql_indices("SELECT * FROM \"products,orders\""),
+            ["products", "orders"]
+        );
+        assert_eq!(
+            sql_indices("SELECT * FROM products,orders"),
+            ["products", "orders"]
+        );
+        assert_eq!(
+            sql_indices("SELECT * FROM (SELECT * FROM orders) WHERE x = 1"),
+            ["orders"]
+        );
+        // `FROM` inside other words is not a keyword
+        assert_eq!(
+            sql_indices("SELECT from_date, date_from FROM products"),
+            ["products"]
+        );
+    }
+
+    #[test]
+    fn validate_query_clauses() {
+        assert!(validate_query_clause(&json!({ "match": { "title": "paris" } })).is_ok());
+        assert!(validate_query_clause(&json!({})).is_err());
+        assert!(validate_query_clause(&json!({ "match": {}, "term": {} })).is_err());
+        assert!(validate_query_clause(&json!("paris")).is_err());
+    }
+
+    #[test]
+    fn build_and_flatten_aggregations() {
+        let aggregations: Vec<Aggregation> = serde_json::from_value(json!([
+            {
+                "name": "service",
+                "type": "terms",
+                "field": "service.keyword",
+                "size": 5,
+                "sub_aggregations": [
+                    { "name": "latency", "type": "stats", "field": "latency" }
+                ]
+            },
+            { "name": "users", "type": "cardinality", "field": "user.id" }
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            build_aggregations(&aggregations).unwrap(),
+            json!({
+                "service": {
+                    "terms": { "field": "service.keyword", "size": 5 },
+                    "aggs": { "latency": { "stats": { "field": "latency" } } }
+                },
+                "users": { "cardinality": { "field": "user.id" } }
+            })
+        );
+
+        let results = json!({
+            "service": {
+                "buckets": [
+                    {
+                        "key": "api",
+                        "doc_count": 3,
+                        "latency": { "count": 3, "min": 1.0, "max": 3.0, "avg": 2.0, "sum": 6.0 }
+                    },
+                    {
+                        "key": "web",
+                        "doc_count": 1,
+                        "latency": { "count": 1, "min": 4.0, "max": 4.0, "avg": 4.0, "sum": 4.0 }
+                    }
+                ]
+            },
+            "users": { "value": 2 }
+        });
+        let mut rows = Vec::new();
+        flatten_aggregations(
+            &aggregations,
+            results.as_object().unwrap(),
+            Map::new(),
+            &mut rows,
+        );
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0]["users"], 2);
+        assert_eq!(rows[0]["service"], "api");
+        assert_eq!(rows[0]["service.doc_count"], 3);
+        assert_eq!(rows[0]["latency.avg"], 2.0);
+        assert_eq!(rows[1]["service"], "web");
+        assert_eq!(rows[1]["latency.max"], 4.0);
+    }
+
+    #[test]
+    fn reject_invalid_aggregations() {
+        let invalid = [
+            json!([{ "name": "a.b", "type": "terms", "field": "service" }]),
+            json!([
+                { "name": "a", "type": "terms", "field": "service" },
+                { "name": "a", "type": "stats", "field": "latency" }
+            ]),
+            json!([{ "name": "day", "type": "date_histogram", "field": "@timestamp" }]),
+            json!([{
+                "name": "latency",
+                "type": "stats",
+                "field": "latency",
+                "sub_aggregations": [{ "name": "service", "type": "terms", "field": "service" }]
+            }]),
+        ];
+        for aggregations in invalid {
+            let aggregations: Vec<Aggregation> = serde_json::from_value(aggregations).unwrap();
+            assert!(build_aggregations(&aggregations).is_err());
+        }
+    }
+
+    #[test]
+    fn build_bulk_bodies() {
+        let documents =
+            "{\"id\": \"a\", \"title\": \"Paris\"}\n\n{\"id\": 2, \"title\": \"Rome\"}\n";
+
+        let (lines, ndjson) = build_bulk_body(documents, Some("id")).unwrap();
+        assert_eq!(lines, [1, 3]);
+        let ndjson: Vec<Value> = ndjson
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(ndjson[0], json!({ "index": { "_id": "a" } }));
+        assert_eq!(ndjson[1]["title"], "Paris");
+        assert_eq!(ndjson[2], json!({ "index": { "_id": "2" } }));
+
+        let (_, ndjson) = build_bulk_body(documents, None).unwrap();
+        assert!(ndjson.starts_with("{\"index\":{}}\n"));
+
+        assert!(build_bulk_body("{\"title\": \"Paris\"}", Some("id")).is_err());
+        assert!(build_bulk_body("[1, 2]", None).is_err());
+        assert!(build_bulk_body("\n  \n", None).is_err());
+    }
+
+    #[test]
+    fn escape_csv_values() {
+        assert_eq!(escape_csv("Paris"), "Paris");
+        assert_eq!(escape_csv("Paris, France"), "\"Paris, France\"");
+        assert_eq!(escape_csv("the \"city\""), "\"the \"\"city\"\"\"");
+        assert_eq!(escape_csv("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn render_csv() {
+        let hits = [
+            hit(
+                "1",
+                json!({ "title": "Paris, France", "author": { "name": "Ann" }, "tags": ["a", "b"] }),
+            ),
+            hit("2", json!({ "title": "Rome", "year": 2025 })),
+        ];
+        let fields = ["year".to_string(), "author.*".to_string()];
+
+        assert_eq!(
+            render_hits_as_csv(&hits, Some(&fields)),
+            "_id,year,author.name,tags,title\n\
+             1,,Ann,\"[\"\"a\"\",\"\"b\"\"]\",\"Paris, France\"\n\
+             2,2025,,,Rome\n"
+        );
+    }
+
+    #[test]
+    fn reject_forged_cursors() {
+        assert!(PageCursor::take("forged").is_err());
+
+        let cursor = PageCursor {
+            pit_id: "pit".to_string(),
+            search_after: Some(vec![json!(1)]),
+            query: None,
+            sort: None,
+            source: None,
+            size: 10,
+        };
+        let id = cursor.store();
+        assert_ne!(id, "pit");
+        // a cursor can not be guessed from another one
+        assert!(PageCursor::take(&format!("{id}x")).is_err());
+
+        let cursor = PageCursor::take(&id).unwrap();
+        assert_eq!(cursor.pit_id, "pit");
+        assert_eq!(cursor.size, 10);
+        // a cursor is used once
+        assert!(PageCursor::take(&id).is_err());
+    }
+}
@@ -7,6 +7,7 @@ use rmcp::transport::{
     sse_server::SseServer,
     streamable_http_server::{StreamableHttpService, session::local::LocalSessionManager},
 };
+use std::path::PathBuf;
 use tokio::sync::RwLock;
 use tracing_subscriber::{self, layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -20,21 +21,54 @@ struct Args {
     /// The base URL of the Elasticsearch server
     #[arg(long, default_value = "http://127.0.0.1:9200")]
     base_url: String,
+    /// Path to a PEM file with the CA certificate that signed the certificate of the Elasticsearch server
+    #[arg(long)]
+    ca_cert: Option<PathBuf>,
+    /// Path to a PEM file with the client certificate used to authenticate to the Elasticsearch server
+    #[arg(long, requires = "client_key")]
+    client_cert: Option<PathBuf>,
+    /// Path to a PEM file with the private key of the client certificate
+    #[arg(long, requires = "client_cert")]
+    client_key: Option<PathBuf>,
     /// Socket address to bind to
     #[arg(short, long, default_value = DEFAULT_SOCKET_ADDR)]
     socket_addr: String,
     /// Transport type to use
     #[arg(short, long, value_enum, default_value = "stream-http")]
     transport: TransportType,
-    /// Index to search
+    /// Index to search when no index is given to a tool
     #[arg(long)]
     index: String,
+    /// Names or patterns of the other indices and aliases that the tools are allowed to read, separated by commas, e.g. "logs-*,products"
+    #[arg(long, value_delimiter = ',')]
+    allowed_indices: Vec<String>,
     /// Name of fields to search
     #[arg(long, value_delimiter = ',', default_value = "title,content")]
     fields: Vec<String>,
     /// Maximum number of query results to return
     #[arg(long, default_value = "10")]
     size: u64,
+    /// Name of the field shown as the title of the hits in the text format of the search results
+    #[arg(long, default_value = "title")]
+    title_field: String,
+    /// Maximum number of documents returned by the `export` tool
+    #[arg(long, default_value = "10000")]
+    max_export_rows: u64,
+    /// Enable the tools that create indices and write documents
+    #[arg(long)]
+    allow_write: bool,
+    /// Enable the admin tools that manage index templates, ILM policies, aliases and reindex tasks
+    #[arg(long)]
+    enable_admin_tools: bool,
+    /// The base URL of the embedding server used by the `semantic_search` tool, e.g., "https://api.openai.com/v1"
+    #[arg(long, requires = "vector_field")]
+    embedding_service: Option<String>,
+    /// The name of the embedding model. Defaults to the default model of the embedding server
+    #[arg(long, requires = "embedding_service")]
+    embedding_model: Option<String>,
+    /// Name of the `dense_vector` field that holds the embeddings of the documents
+    #[arg(long, requires = "embedding_service")]
+    vector_field: Option<String>,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -53,37 +87,116 @@ async fn main() -> anyhow::Result<()> {
         .with(tracing_subscriber::fmt::layer().with_target(true))
         .init();
 
-    let api_key = match std::env::var("ES_API_KEY") {
-        Ok(api_key) => format!("ApiKey {api_key}"),
-        Err(_) => {
-            let err_message = "The environment variable `ES_API_KEY` is not set";
+    let args = Args::parse();
+
+    let auth = parse_auth()?;
+    tracing::info!(
+        "Authentication to the Elasticsearch server: {}",
+        auth.scheme()
+    );
 
-            tracing::error!("{}", err_message);
+    let client = build_http_client(&args)?;
 
-            return Err(anyhow::anyhow!(err_message));
-        }
+    // the semantic search tool is enabled if an embedding service and a vector field are given
+    let semantic_search = match (args.embedding_service, args.vector_field) {
+        (Some(embedding_service), Some(vector_field)) => Some(SemanticSearchConfig {
+            embedding_service: ServiceConfig {
+                url: embedding_service,
+                api_key: std::env::var("EMBEDDING_SERVICE_API_KEY").ok(),
+            },
+            embedding_model: args.embedding_model,
+            vector_field,
+        }),
+        _ => None,
     };
+    let enable_semantic_search = semantic_search.is_some();
 
-    let args = Args::parse();
+    // the default index is always allowed
+    let mut allowed_indices = vec![args.index.clone()];
+    for index in args.allowed_indices {
+        if !allowed_indices.contains(&index) {
+            allowed_indices.push(index);
+        }
+    }
+    tracing::info!("Allowed indices: {}", allowed_indices.join(", "));
 
     let connection_config = ConnectionConfig {
         base_url: args.base_url,
-        api_key: Some(api_key),
+        auth,
+        client,
         index: args.index,
+        allowed_indices,
         fields: args.fields,
         size: args.size,
+        title_field: args.title_field,
+        max_export_rows: args.max_export_rows,
+        semantic_search,
     };
 
+    // check that the fields to search exist in the mapping of the index. The fields may have a
+    // boost, e.g. `title^2`
+    let field_names: Vec<String> = connection_config
+        .fields
+        .iter()
+        .map(|field| field.split('^').next().unwrap_or(field).to_string())
+        .collect();
+    match elastic::get_field_caps(
+        &connection_config,
+        &connection_config.index,
+        Some(&field_names),
+    )
+    .await
+    {
+        Ok(capabilities) => {
+            for name in &field_names {
+                let searchable = capabilities.iter().any(|capability| {
+                    capability.searchable && elastic::matches_pattern(name, &capability.name)
+                });
+                if !searchable {
+                    let err_message = format!(
+                        "The field `{name}` is not a searchable field of the index `{}`. Use the `field_caps` tool to find the searchable fields",
+                        connection_config.index
+                    );
+
+                    tracing::error!("{}", err_message);
+
+                    return Err(anyhow::anyhow!(err_message));
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Skip checking the fields to search: {}", e.message);
+        }
+    }
+
     CONNECTION_CONFIG
         .set(RwLock::new(connection_config))
         .unwrap();
 
+    let allow_write = args.allow_write;
+    if allow_write {
+        tracing::info!("Write tools are enabled");
+    }
+    let enable_admin_tools = args.enable_admin_tools;
+    if enable_admin_tools {
+        tracing::info!("Admin tools are enabled");
+    }
+    if enable_semantic_search {
+        tracing::info!("Semantic search is enabled");
+    }
+
     tracing::info!("Starting Cardea Elastic MCP server on {}", args.socket_addr);
 
     match args.transport {
         TransportType::StreamHttp => {
             let service = StreamableHttpService::new(
-                || Ok(ElasticSearchServer::new()),
+                move || {
+                    Ok(ElasticSearchServer::new(
+                        allow_write,
+                        enable_semantic_search,
+                        enable_admin_tools,
+                    ))
+                },
                 LocalSessionManager::default().into(),
                 Default::default(),
             );
@@ -97,7 +210,13 @@ async fn main() -> anyhow::Result<()> {
         TransportType::Sse => {
             let ct = SseServer::serve(args.socket_addr.parse()?)
                 .await?
-                .with_service(ElasticSearchServer::new);
+                .with_service(move || {
+                    ElasticSearchServer::new(
+                        allow_write,
+                        enable_semantic_search,
+                        enable_admin_tools,
+                    )
+                });
 
             tokio::signal::ctrl_c().await?;
             ct.cancel();
@@ -107,14 +226,135 @@ async fn main() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Read the credentials of the Elasticsearch server from the environment variables
+fn parse_auth() -> anyhow::Result<Auth> {
+    let api_key = std::env::var("ES_API_KEY").ok();
+    let bearer_token = std::env::var("ES_BEARER_TOKEN").ok();
+    let username = std::env::var("ES_USERNAME").ok();
+    let password = std::env::var("ES_PASSWORD").ok();
+
+    let auth = match (api_key, bearer_token, username, password) {
+        (None, None, None, None) => Auth::None,
+        (Some(api_key), None, None, None) => Auth::ApiKey(api_key),
+        (None, Some(token), None, None) => Auth::Bearer(token),
+        (None, None, Some(username), Some(password)) => Auth::Basic { username, password },
+        (None, None, Some(_), None) | (None, None, None, Some(_)) => {
+            let err_message =
+                "Both `ES_USERNAME` and `ES_PASSWORD` must be set to use basic authentication";
+
+            tracing::error!("{}", err_message);
+
+            return Err(anyhow::anyhow!(err_message));
+        }
+        _ => {
+            let err_message = "Only one of `ES_API_KEY`, `ES_BEARER_TOKEN` and `ES_USERNAME`/`ES_PASSWORD` can be set";
+
+            tracing::error!("{}", err_message);
+
+            return Err(anyhow::anyhow!(err_message));
+        }
+    };
+
+    Ok(auth)
+}
+
+/// Build the HTTP client used to connect to the Elasticsearch server, with the CA certificate and
+/// the client certificate if given
+fn build_http_client(args: &Args) -> anyhow::Result<reqwest::Client> {
+    let mut builder = reqwest::Client::builder().use_rustls_tls();
+
+    if let Some(ca_cert) = &args.ca_cert {
+        let pem = std::fs::read(ca_cert).map_err(|e| {
+            anyhow::anyhow!(
+                "Failed to read the CA certificate `{}`: {e}",
+                ca_cert.display()
+            )
+        })?;
+        let certificate = reqwest::Certificate::from_pem(&pem)
+            .map_err(|e| anyhow::anyhow!("Invalid CA certificate `{}`: {e}", ca_cert.display()))?;
+        builder = builder.add_root_certificate(certificate);
+    }
+
+    if let (Some(client_cert), Some(client_key)) = (&args.client_cert, &args.client_key) {
+        // the identity is built from a PEM buffer that holds both the certificate and the key
+        let mut pem = Vec::new();
+        for path in [client_cert, client_key] {
+            let mut content = std::fs::read(path)
+                .map_err(|e| anyhow::anyhow!("Failed to read `{}`: {e}", path.display()))?;
+            pem.append(&mut content);
+            pem.push(b'\n');
+        }
+        let identity = reqwest::Identity::from_pem(&pem)
+            .map_err(|e| anyhow::anyhow!("Invalid client certificate or key: {e}"))?;
+        builder = builder.identity(identity);
+    }
+
+    builder
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to build the HTTP client: {e}"))
+}
+
 #[derive(Debug, Clone)]
 pub struct ConnectionConfig {
     pub base_url: String,
-    pub api_key: Option<String>,
-    /// index name
+    /// credentials of the Elasticsearch server
+    pub auth: Auth,
+    /// client with the TLS settings of the Elasticsearch server
+    pub client: reqwest::Client,
+    /// the default index name
     pub index: String,
+    /// names or patterns of the indices and aliases that the tools are allowed to read,
+    /// including the default index
+    pub allowed_indices: Vec<String>,
     /// name of fields to search
     pub fields: Vec<String>,
     /// number of results to return
     pub size: u64,
+    /// name of the field shown as the title of the hits in the text format
+    pub title_field: String,
+    /// maximum number of documents returned by the `export` tool
+    pub max_export_rows: u64,
+    /// semantic search config. `None` if semantic search is disabled
+    pub semantic_search: Option<SemanticSearchConfig>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SemanticSearchConfig {
+    pub embedding_service: ServiceConfig,
+    /// name of the embedding model
+    pub embedding_model: Option<String>,
+    /// name of the `dense_vector` field
+    pub vector_field: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServiceConfig {
+    pub url: String,
+    pub api_key: Option<String>,
+}
+
+/// How to authenticate to the Elasticsearch server
+#[derive(Clone)]
+pub enum Auth {
+    None,
+    Basic { username: String, password: String },
+    ApiKey(String),
+    Bearer(String),
+}
+impl Auth {
+    /// The name of the authentication scheme
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            Auth::None => "none",
+            Auth::Basic { .. } => "basic",
+            Auth::ApiKey(_) => "api key",
+            Auth::Bearer(_) => "bearer token",
+        }
+    }
+}
+impl std::fmt::Debug for Auth {
+    // never print the credentials
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Auth({})", self.scheme())
+    }
 }
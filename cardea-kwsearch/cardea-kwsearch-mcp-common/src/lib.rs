@@ -116,3 +116,83 @@ impl From<SearchHit> for KwSearchHit {
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IndexFilesRequest {
+    #[schemars(
+        description = "The path of the file or the directory to index, relative to the files directory of the server. Directories are read recursively"
+    )]
+    pub path: String,
+    #[schemars(
+        description = "The name of the index to create with the chunks. An existing index with this name is replaced. The index searched by the server can only be replaced if the server allows it"
+    )]
+    pub index: String,
+    #[schemars(
+        description = "How to split the files into chunks: `tokens` (default), `sentences` or `headings`"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<ChunkStrategy>,
+    #[schemars(description = "The maximum number of tokens of a chunk. Defaults to 200")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_size: Option<usize>,
+    #[schemars(
+        description = "The number of tokens shared by two consecutive chunks. Defaults to 20"
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overlap: Option<usize>,
+}
+
+/// How to split a file into chunks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkStrategy {
+    /// Chunks of a fixed number of tokens
+    #[default]
+    Tokens,
+    /// Chunks of whole sentences
+    Sentences,
+    /// One chunk per section under a heading, split by tokens if the section is too long
+    Headings,
+}
+impl std::str::FromStr for ChunkStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "tokens" => Ok(Self::Tokens),
+            "sentences" => Ok(Self::Sentences),
+            "headings" => Ok(Self::Headings),
+            _ => Err(format!(
+                "invalid chunk strategy `{s}`: expected `tokens`, `sentences` or `headings`"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct IndexFilesResponse {
+    #[schemars(description = "The name of the index")]
+    pub index: String,
+    #[schemars(description = "The number of files read")]
+    pub files: usize,
+    #[schemars(description = "The number of chunks sent to the index")]
+    pub chunks: usize,
+    #[schemars(description = "The files that were not indexed, with the reason")]
+    pub skipped_files: Vec<SkippedFile>,
+    #[schemars(description = "The chunks that failed to be indexed")]
+    pub failed_chunks: Vec<KwDocumentResult>,
+}
+impl From<CallToolResult> for IndexFilesResponse {
+    fn from(value: CallToolResult) -> Self {
+        let content = value.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<IndexFilesResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SkippedFile {
+    #[schemars(description = "The path of the file, relative to the files directory")]
+    pub path: String,
+    #[schemars(description = "Why the file was not indexed")]
+    pub reason: String,
+}
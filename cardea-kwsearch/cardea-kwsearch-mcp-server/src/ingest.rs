@@ -0,0 +1,815 @@
+use cardea_kwsearch_mcp_common::{ChunkStrategy, SkippedFile};
+use endpoints::rag::keyword_search::DocumentInput;
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_CHUNK_SIZE: usize = 200;
+pub const DEFAULT_OVERLAP: usize = 20;
+/// The maximum number of chunks an index is created with, to bound the size of the request
+pub const MAX_CHUNKS: usize = 10_000;
+
+/// The extensions of the files that can be indexed
+const SUPPORTED_EXTENSIONS: [&str; 6] = ["txt", "md", "markdown", "html", "htm", "jsonl"];
+
+/// How to split the files into chunks
+#[derive(Debug, Clone, Copy)]
+pub struct ChunkOptions {
+    pub strategy: ChunkStrategy,
+    /// maximum number of tokens of a chunk
+    pub chunk_size: usize,
+    /// number of tokens shared by two consecutive chunks
+    pub overlap: usize,
+}
+impl ChunkOptions {
+    pub fn new(
+        strategy: Option<ChunkStrategy>,
+        chunk_size: Option<usize>,
+        overlap: Option<usize>,
+    ) -> Result<Self, String> {
+        let chunk_size = chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE);
+        let overlap = overlap.unwrap_or(DEFAULT_OVERLAP.min(chunk_size / 2));
+        if chunk_size == 0 {
+            return Err("`chunk_size` must be greater than 0".to_string());
+        }
+        if overlap >= chunk_size {
+            return Err(format!(
+                "`overlap` must be less than `chunk_size` ({chunk_size})"
+            ));
+        }
+
+        Ok(Self {
+            strategy: strategy.unwrap_or_default(),
+            chunk_size,
+            overlap,
+        })
+    }
+}
+
+/// The chunks of the files read from a directory
+#[derive(Debug)]
+pub struct Ingestion {
+    /// number of files read
+    pub files: usize,
+    /// the chunks, with the title and the path of their file in their title
+    pub documents: Vec<DocumentInput>,
+    pub skipped_files: Vec<SkippedFile>,
+}
+
+/// Resolve a path relative to the files directory, and return the canonical paths of the
+/// directory and of the file or directory. The path must not lead outside of the files directory,
+/// e.g. with `..` or a symbolic link.
+pub fn resolve_path(files_dir: &Path, path: &str) -> Result<(PathBuf, PathBuf), String> {
+    let root = files_dir.canonicalize().map_err(|e| {
+        format!(
+            "Failed to read the files directory `{}`: {e}",
+            files_dir.display()
+        )
+    })?;
+
+    // an absolute path would replace the files directory
+    let resolved = root
+        .join(path.trim_start_matches('/'))
+        .canonicalize()
+        .map_err(|e| format!("Failed to read `{path}`: {e}"))?;
+    if !resolved.starts_with(&root) {
+        return Err(format!(
+            "The path `{path}` is outside of the files directory"
+        ));
+    }
+
+    Ok((root, resolved))
+}
+
+/// Read the supported files of a file or a directory, and split them into chunks. The paths in the
+/// titles of the chunks are relative to `root`.
+pub fn read_files(root: &Path, path: &Path, options: ChunkOptions) -> Result<Ingestion, String> {
+    let mut files = Vec::new();
+    match path.is_dir() {
+        true => collect_files(path, &mut files)
+            .map_err(|e| format!("Failed to read the directory `{}`: {e}", path.display()))?,
+        false => files.push(path.to_path_buf()),
+    }
+
+    let mut ingestion = Ingestion {
+        files: 0,
+        documents: Vec::new(),
+        skipped_files: Vec::new(),
+    };
+    for file in files {
+        let relative_path = file
+            .strip_prefix(root)
+            .unwrap_or(&file)
+            .display()
+            .to_string();
+
+        if !is_supported(&file) {
+            ingestion.skipped_files.push(SkippedFile {
+                path: relative_path,
+                reason: format!(
+                    "unsupported file type. Supported extensions: {}",
+                    SUPPORTED_EXTENSIONS.join(", ")
+                ),
+            });
+            continue;
+        }
+
+        match load_file(&file) {
+            Ok(sources) => {
+                ingestion.files += 1;
+                for source in sources {
+                    for chunk in chunk_text(&source.text, &options) {
+                        let title = match chunk.heading {
+                            Some(heading) if heading != source.title => {
+                                format!("{} - {heading} ({relative_path})", source.title)
+                            }
+                            _ => format!("{} ({relative_path})", source.title),
+                        };
+                        ingestion.documents.push(DocumentInput {
+                            content: chunk.text,
+                            title: Some(title),
+                        });
+                    }
+                }
+            }
+            Err(reason) => ingestion.skipped_files.push(SkippedFile {
+                path: relative_path,
+                reason,
+            }),
+        }
+    }
+
+    Ok(ingestion)
+}
+
+/// Collect the supported files of a directory and of its sub-directories, in order. Hidden files
+/// and symbolic links are skipped.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    let mut entries = std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        // the file type of a symbolic link is not the type of its target
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else if file_type.is_file() && is_supported(&entry.path()) {
+            files.push(entry.path());
+        }
+    }
+
+    Ok(())
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+fn is_supported(path: &Path) -> bool {
+    SUPPORTED_EXTENSIONS.contains(&extension(path).as_str())
+}
+
+/// A text read from a file, e.g. a whole Markdown file or a line of a JSONL file
+struct SourceDocument {
+    title: String,
+    text: String,
+}
+
+/// Read a file as text. The title is the first heading of the file, or its name
+fn load_file(path: &Path) -> Result<Vec<SourceDocument>, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let sources = match extension(path).as_str() {
+        "md" | "markdown" => vec![SourceDocument {
+            title: first_heading(&content).unwrap_or(stem),
+            text: content,
+        }],
+        "html" | "htm" => {
+            let (title, text) = html_to_text(&content);
+            vec![SourceDocument {
+                title: title.or_else(|| first_heading(&text)).unwrap_or(stem),
+                text,
+            }]
+        }
+        "jsonl" => {
+            let mut sources = Vec::new();
+            for (number, line) in content.lines().enumerate() {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let line_number = number + 1;
+
+                let value: Value = serde_json::from_str(line)
+                    .map_err(|e| format!("invalid JSON at line {line_number}: {e}"))?;
+                let text = ["content", "text"]
+                    .iter()
+                    .find_map(|field| value.get(field)?.as_str())
+                    .ok_or_else(|| {
+                        format!("no `content` or `text` string field at line {line_number}")
+                    })?;
+                let title = match value.get("title").and_then(Value::as_str) {
+                    Some(title) => title.to_string(),
+                    None => format!("{stem} #{line_number}"),
+                };
+
+                sources.push(SourceDocument {
+                    title,
+                    text: text.to_string(),
+                });
+            }
+            sources
+        }
+        _ => vec![SourceDocument {
+            title: stem,
+            text: content,
+        }],
+    };
+
+    Ok(sources)
+}
+
+/// A chunk of a text, with the heading of the section it belongs to
+struct Chunk {
+    heading: Option<String>,
+    text: String,
+}
+
+fn chunk_text(text: &str, options: &ChunkOptions) -> Vec<Chunk> {
+    let ChunkOptions {
+        strategy,
+        chunk_size,
+        overlap,
+    } = *options;
+
+    let chunks: Vec<Chunk> = match strategy {
+        ChunkStrategy::Tokens => chunk_by_tokens(text, chunk_size, overlap)
+            .into_iter()
+            .map(|text| Chunk {
+                heading: None,
+                text,
+            })
+            .collect(),
+        ChunkStrategy::Sentences => chunk_by_sentences(text, chunk_size, overlap)
+            .into_iter()
+            .map(|text| Chunk {
+                heading: None,
+                text,
+            })
+            .collect(),
+        // the sections longer than a chunk are split by tokens
+        ChunkStrategy::Headings => sections(text)
+            .into_iter()
+            .flat_map(|(heading, body)| {
+                chunk_by_tokens(&body, chunk_size, overlap)
+                    .into_iter()
+                    .map(move |text| Chunk {
+                        heading: heading.clone(),
+                        text,
+                    })
+            })
+            .collect(),
+    };
+
+    chunks
+        .into_iter()
+        .filter(|chunk| !chunk.text.trim().is_empty())
+        .collect()
+}
+
+/// The byte ranges of the tokens of a text: the words separated by whitespaces, and each CJK
+/// character, since CJK texts have no whitespaces between words
+fn token_spans(text: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        if c.is_whitespace() {
+            if let Some(start) = start.take() {
+                spans.push((start, i));
+            }
+        } else if is_cjk(c) {
+            if let Some(start) = start.take() {
+                spans.push((start, i));
+            }
+            spans.push((i, i + c.len_utf8()));
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(start) = start {
+        spans.push((start, text.len()));
+    }
+
+    spans
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}' // Hiragana and Katakana
+        | '\u{3400}'..='\u{4DBF}' // CJK Unified Ideographs Extension A
+        | '\u{4E00}'..='\u{9FFF}' // CJK Unified Ideographs
+        | '\u{AC00}'..='\u{D7AF}' // Hangul Syllables
+        | '\u{F900}'..='\u{FAFF}' // CJK Compatibility Ideographs
+    )
+}
+
+fn token_count(text: &str) -> usize {
+    token_spans(text).len()
+}
+
+/// Split a text into chunks of `chunk_size` tokens, the last `overlap` tokens of a chunk being the
+/// first ones of the next chunk. The whitespaces between the tokens are kept.
+fn chunk_by_tokens(text: &str, chunk_size: usize, overlap: usize) -> Vec<String> {
+    let spans = token_spans(text);
+
+    let mut chunks = Vec::new();
+    let mut begin = 0;
+    while begin < spans.len() {
+        let end = (begin + chunk_size).min(spans.len());
+        chunks.push(text[spans[begin].0..spans[end - 1].1].to_string());
+        if end == spans.len() {
+            break;
+        }
+        begin = end - overlap;
+    }
+
+    chunks
+}
+
+/// Split a text into chunks of whole sentences of at most `chunk_size` tokens. A chunk starts with
+/// the last sentences of the previous chunk, up to `overlap` tokens. A sentence longer than a
+/// chunk is split by tokens.
+fn chunk_by_sentences(text: &str, chunk_size: usize, overlap: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current: Vec<(&str, usize)> = Vec::new();
+    let mut tokens = 0;
+
+    for sentence in split_sentences(text) {
+        let count = token_count(sentence);
+
+        if count > chunk_size {
+            if !current.is_empty() {
+                chunks.push(join_sentences(&current));
+            }
+            chunks.extend(chunk_by_tokens(sentence, chunk_size, overlap));
+            current.clear();
+            tokens = 0;
+            continue;
+        }
+
+        if tokens + count > chunk_size && !current.is_empty() {
+            chunks.push(join_sentences(&current));
+
+            // keep the last sentences that fit in the overlap
+            let mut kept = 0;
+            let mut kept_tokens = 0;
+            for (_, sentence_tokens) in current.iter().rev() {
+                if kept_tokens + sentence_tokens > overlap {
+                    break;
+                }
+                kept += 1;
+                kept_tokens += sentence_tokens;
+            }
+            current.drain(..current.len() - kept);
+            tokens = kept_tokens;
+
+            while tokens + count > chunk_size {
+                let (_, sentence_tokens) = current.remove(0);
+                tokens -= sentence_tokens;
+            }
+        }
+
+        current.push((sentence, count));
+        tokens += count;
+    }
+    if !current.is_empty() {
+        chunks.push(join_sentences(&current));
+    }
+
+    chunks
+}
+
+fn join_sentences(sentences: &[(&str, usize)]) -> String {
+    sentences
+        .iter()
+        .map(|(sentence, _)| *sentence)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split a text into sentences, at the end of sentence punctuation and at the blank lines
+fn split_sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let boundary = match c {
+            '。' | '！' | '？' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            '\n' => chars.peek().is_some_and(|(_, next)| *next == '\n'),
+            _ => false,
+        };
+        if boundary {
+            let end = i + c.len_utf8();
+            let sentence = text[start..end].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+            start = end;
+        }
+    }
+    let sentence = text[start..].trim();
+    if !sentence.is_empty() {
+        sentences.push(sentence);
+    }
+
+    sentences
+}
+
+/// Split a Markdown text into sections, each with the heading it starts with. A heading directly
+/// followed by a sub-heading is kept in the section of the sub-heading.
+fn sections(text: &str) -> Vec<(Option<String>, String)> {
+    let mut sections = Vec::new();
+    let mut heading = None;
+    let mut body = String::new();
+    let mut has_content = false;
+    let mut in_code_block = false;
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+        }
+
+        match markdown_heading(line).filter(|_| !in_code_block) {
+            Some(title) => {
+                if has_content {
+                    sections.push((heading.take(), std::mem::take(&mut body)));
+                    has_content = false;
+                }
+                heading = Some(title);
+            }
+            None => has_content |= !line.trim().is_empty(),
+        }
+        body.push_str(line);
+        body.push('\n');
+    }
+    if has_content {
+        sections.push((heading, body));
+    }
+
+    sections
+}
+
+/// The text of a Markdown heading line, e.g. `Install` for `## Install`
+fn markdown_heading(line: &str) -> Option<String> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+
+    let title = rest.trim().trim_end_matches('#').trim();
+    match title.is_empty() {
+        true => None,
+        false => Some(title.to_string()),
+    }
+}
+
+fn first_heading(text: &str) -> Option<String> {
+    sections(text).into_iter().find_map(|(heading, _)| heading)
+}
+
+/// Convert an HTML page to text, with its headings as Markdown headings so that the page can be
+/// split by headings. Returns the title of the page, if any, and the text.
+fn html_to_text(html: &str) -> (Option<String>, String) {
+    // the lowercase copy has the same byte offsets, to find the closing tags
+    let lowercase = html.to_ascii_lowercase();
+    let mut title = None;
+    let mut text = String::new();
+    let mut position = 0;
+
+    while let Some(offset) = html[position..].find('<') {
+        let start = position + offset;
+        push_text(&mut text, &html[position..start]);
+
+        if html[start..].starts_with("<!--") {
+            position = match html[start..].find("-->") {
+                Some(end) => start + end + 3,
+                None => html.len(),
+            };
+            continue;
+        }
+        let Some(end) = html[start..].find('>').map(|end| start + end) else {
+            position = html.len();
+            break;
+        };
+        position = end + 1;
+
+        let tag = &lowercase[start + 1..end];
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match name {
+            // the content of these elements is not text
+            "title" | "script" | "style" | "noscript" | "template" | "svg" if !closing => {
+                let closing_tag = format!("</{name}");
+                let content_end = lowercase[position..]
+                    .find(&closing_tag)
+                    .map(|offset| position + offset)
+                    .unwrap_or(html.len());
+                if name == "title" {
+                    let page_title = decode_entities(html[position..content_end].trim());
+                    if !page_title.is_empty() {
+                        title = Some(page_title);
+                    }
+                }
+                position = lowercase[content_end..]
+                    .find('>')
+                    .map(|offset| content_end + offset + 1)
+                    .unwrap_or(html.len());
+            }
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                text.push_str("\n\n");
+                if !closing {
+                    let level = name[1..].parse::<usize>().unwrap_or(1);
+                    text.push_str(&"#".repeat(level));
+                    text.push(' ');
+                }
+            }
+            "li" if !closing => text.push_str("\n- "),
+            "td" | "th" => text.push(' '),
+            "p" | "div" | "br" | "tr" | "ul" | "ol" | "table" | "section" | "article"
+            | "header" | "footer" | "blockquote" | "pre" | "hr" => text.push('\n'),
+            _ => {}
+        }
+    }
+    if position < html.len() {
+        push_text(&mut text, &html[position..]);
+    }
+
+    // trim the lines, and keep at most one blank line between paragraphs
+    let mut cleaned = String::new();
+    let mut blank = true;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            if !blank {
+                cleaned.push('\n');
+            }
+            blank = true;
+        } else {
+            cleaned.push_str(line);
+            cleaned.push('\n');
+            blank = false;
+        }
+    }
+
+    (title, cleaned.trim_end().to_string())
+}
+
+/// Append a text node of an HTML page, with its whitespaces collapsed as a browser does
+fn push_text(text: &mut String, node: &str) {
+    let decoded = decode_entities(node);
+    let mut last_whitespace = text.ends_with(char::is_whitespace);
+    for c in decoded.chars() {
+        match c.is_whitespace() {
+            true if last_whitespace => {}
+            true => {
+                text.push(' ');
+                last_whitespace = true;
+            }
+            false => {
+                text.push(c);
+                last_whitespace = false;
+            }
+        }
+    }
+}
+
+/// Decode the common named and the numeric character references of HTML
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .find(';')
+            .filter(|end| *end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let character = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => entity.strip_prefix('#')?.parse().ok(),
+                };
+                code.and_then(char::from_u32)
+            }
+        });
+
+        match (entity, character) {
+            (Some(entity), Some(character)) => {
+                decoded.push(character);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    decoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A new empty directory in the temporary directory
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("cardea-kwsearch-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolve_path_inside_files_dir() {
+        let dir = temp_dir("resolve-inside");
+        let files_dir = dir.join("files");
+        std::fs::create_dir_all(files_dir.join("guides")).unwrap();
+        std::fs::write(files_dir.join("guides/install.md"), "# Install").unwrap();
+
+        let (root, path) = resolve_path(&files_dir, "guides/../guides/install.md").unwrap();
+        assert_eq!(root, files_dir.canonicalize().unwrap());
+        assert_eq!(path, root.join("guides/install.md"));
+
+        // an absolute path is relative to the files directory
+        let (root, path) = resolve_path(&files_dir, "/guides").unwrap();
+        assert_eq!(path, root.join("guides"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn resolve_path_outside_files_dir() {
+        let dir = temp_dir("resolve-outside");
+        let files_dir = dir.join("files");
+        std::fs::create_dir_all(&files_dir).unwrap();
+        std::fs::write(dir.join("secret.txt"), "secret").unwrap();
+
+        let error = resolve_path(&files_dir, "../secret.txt").unwrap_err();
+        assert!(error.contains("outside of the files directory"), "{error}");
+
+        // the absolute path of the secret file does not exist in the files directory
+        let secret = dir.join("secret.txt").display().to_string();
+        assert!(resolve_path(&files_dir, &secret).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolve_path_through_symlink_outside_files_dir() {
+        let dir = temp_dir("resolve-symlink");
+        let files_dir = dir.join("files");
+        std::fs::create_dir_all(&files_dir).unwrap();
+        std::fs::write(dir.join("secret.txt"), "secret").unwrap();
+
+        std::os::unix::fs::symlink(dir.join("secret.txt"), files_dir.join("link.txt")).unwrap();
+        let error = resolve_path(&files_dir, "link.txt").unwrap_err();
+        assert!(error.contains("outside of the files directory"), "{error}");
+
+        std::os::unix::fs::symlink(&dir, files_dir.join("parent")).unwrap();
+        let error = resolve_path(&files_dir, "parent/secret.txt").unwrap_err();
+        assert!(error.contains("outside of the files directory"), "{error}");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn chunk_by_tokens_with_overlap() {
+        let chunks = chunk_by_tokens("a b c d e f g", 3, 1);
+        assert_eq!(chunks, ["a b c", "c d e", "e f g"]);
+
+        // the last chunk may be shorter
+        let chunks = chunk_by_tokens("a b c d e f", 4, 2);
+        assert_eq!(chunks, ["a b c d", "c d e f"]);
+
+        // each CJK character is a token
+        let chunks = chunk_by_tokens("人工智能", 2, 0);
+        assert_eq!(chunks, ["人工", "智能"]);
+    }
+
+    #[test]
+    fn chunk_by_tokens_terminates() {
+        assert!(chunk_by_tokens("", 3, 1).is_empty());
+        assert!(chunk_by_tokens(" \n\t ", 3, 1).is_empty());
+        assert_eq!(chunk_by_tokens("a b", 5, 4), ["a b"]);
+
+        // the largest overlap moves one token forward per chunk
+        let text = (0..100)
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let chunks = chunk_by_tokens(&text, 10, 9);
+        assert_eq!(chunks.len(), 91);
+        assert!(chunks.last().unwrap().ends_with("99"));
+    }
+
+    #[test]
+    fn chunk_by_sentences_with_overlap() {
+        let text = "One two. Three four. Five six.";
+        let chunks = chunk_by_sentences(text, 4, 2);
+        assert_eq!(chunks, ["One two. Three four.", "Three four. Five six."]);
+
+        // the sentences longer than the overlap are not repeated
+        let chunks = chunk_by_sentences(text, 4, 1);
+        assert_eq!(chunks, ["One two. Three four.", "Five six."]);
+
+        // a sentence longer than a chunk is split by tokens
+        let chunks = chunk_by_sentences("Short one. A b c d e f.", 3, 1);
+        assert_eq!(chunks, ["Short one.", "A b c", "c d e", "e f."]);
+    }
+
+    #[test]
+    fn chunk_by_sentences_terminates() {
+        assert!(chunk_by_sentences("", 3, 1).is_empty());
+
+        let text = "A b c d. E f g h. I j k l.";
+        let chunks = chunk_by_sentences(text, 4, 3);
+        assert_eq!(chunks, ["A b c d.", "E f g h.", "I j k l."]);
+    }
+
+    #[test]
+    fn sections_skip_headings_in_fenced_code() {
+        let text = "# Guide\n\n## Install\nRun:\n```sh\n# not a heading\n```\n~~~\n## nor this\n~~~\n## Usage\nSearch.\n";
+        let sections = sections(text);
+
+        assert_eq!(sections.len(), 2);
+        // the heading of the guide is directly followed by a sub-heading
+        assert_eq!(sections[0].0.as_deref(), Some("Install"));
+        assert!(sections[0].1.starts_with("# Guide\n"));
+        assert!(sections[0].1.contains("# not a heading"));
+        assert!(sections[0].1.contains("## nor this"));
+        assert_eq!(
+            sections[1],
+            (Some("Usage".to_string()), "## Usage\nSearch.\n".to_string())
+        );
+    }
+
+    #[test]
+    fn html_to_text_keeps_headings_and_text() {
+        let html = r#"<!DOCTYPE html>
+<html>
+<head><title>My &amp; Page</title><style>p { color: red; }</style></head>
+<body>
+  <!-- <h1>Hidden</h1> -->
+  <H1 class="title">Intro</H1>
+  <p>Hello&nbsp;<b>world</b>,
+     again.</p>
+  <script>var h = "<h2>not a heading</h2>";</script>
+  <ul><li>one</li><li>two</li></ul>
+</body>
+</html>"#;
+
+        let (title, text) = html_to_text(html);
+        assert_eq!(title.as_deref(), Some("My & Page"));
+        assert_eq!(text, "# Intro\n\nHello world, again.\n\n- one\n- two");
+    }
+
+    #[test]
+    fn decode_html_entities() {
+        assert_eq!(decode_entities("&lt;a&gt; &quot;&apos;"), "<a> \"'");
+        assert_eq!(decode_entities("&#65;&#x42;&#X43;"), "ABC");
+        // unknown and unterminated references are kept
+        assert_eq!(decode_entities("&unknown; & &amp"), "&unknown; & &amp");
+        assert_eq!(decode_entities("&#xFFFFFFFF;"), "&#xFFFFFFFF;");
+    }
+}
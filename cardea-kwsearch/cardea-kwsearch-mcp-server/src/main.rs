@@ -1,12 +1,18 @@
+mod ingest;
 mod search;
 
-use clap::{Parser, ValueEnum};
+use cardea_kwsearch_mcp_common::ChunkStrategy;
+use clap::{Parser, Subcommand, ValueEnum};
+use ingest::{ChunkOptions, DEFAULT_CHUNK_SIZE, DEFAULT_OVERLAP};
 use once_cell::sync::OnceCell;
 use rmcp::transport::{
     sse_server::SseServer,
     streamable_http_server::{StreamableHttpService, session::local::LocalSessionManager},
 };
-use search::{ConnectionConfig, KeywordSearchServer, set_search_tool_prompt};
+use search::{
+    ConnectionConfig, KeywordSearchServer, create_index_from_chunks, set_search_tool_prompt,
+};
+use std::path::PathBuf;
 use tokio::sync::RwLock as TokioRwLock;
 use tracing_subscriber::{self, layer::SubscriberExt, util::SubscriberInitExt};
 
@@ -38,6 +44,35 @@ struct Args {
         default_value = "Please extract 3 to 5 keywords from my question, separated by spaces. Then, try to return a tool call that invokes the keyword search tool.\n\nMy question is: {query}"
     )]
     search_tool_prompt: String,
+    /// Directory that the `index_files` tool reads files from. The tool is disabled if not given
+    #[arg(long)]
+    files_dir: Option<PathBuf>,
+    /// Allow the `index_files` tool and the `index-files` subcommand to recreate the index given by `--index`
+    #[arg(long)]
+    allow_recreate_index: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Create an index from the text, Markdown, HTML and JSONL files of a file or a directory, then exit
+    IndexFiles {
+        /// The file or directory to index. Relative to `--files-dir` if given
+        path: PathBuf,
+        /// The name of the index to create. An existing index with this name is replaced
+        #[arg(long)]
+        target_index: String,
+        /// How to split the files into chunks: `tokens`, `sentences` or `headings`
+        #[arg(long, default_value = "tokens")]
+        strategy: ChunkStrategy,
+        /// Maximum number of tokens of a chunk
+        #[arg(long, default_value_t = DEFAULT_CHUNK_SIZE)]
+        chunk_size: usize,
+        /// Number of tokens shared by two consecutive chunks
+        #[arg(long, default_value_t = DEFAULT_OVERLAP)]
+        overlap: usize,
+    },
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -66,8 +101,29 @@ async fn main() -> anyhow::Result<()> {
         api_key: None,
         index: args.index,
         limit: args.limit,
+        files_dir: args.files_dir,
+        allow_recreate_index: args.allow_recreate_index,
     };
 
+    if let Some(Command::IndexFiles {
+        path,
+        target_index,
+        strategy,
+        chunk_size,
+        overlap,
+    }) = args.command
+    {
+        return index_files(
+            &connection_config,
+            path,
+            &target_index,
+            ChunkOptions::new(Some(strategy), Some(chunk_size), Some(overlap))
+                .map_err(|e| anyhow::anyhow!(e))?,
+        )
+        .await;
+    }
+    let enable_index_files = connection_config.files_dir.is_some();
+
     CONNECTION_CONFIG
         .set(TokioRwLock::new(connection_config))
         .map_err(|_| anyhow::anyhow!("Failed to set CONNECTION_CONFIG"))?;
@@ -80,7 +136,7 @@ async fn main() -> anyhow::Result<()> {
     match args.transport {
         TransportType::StreamHttp => {
             let service = StreamableHttpService::new(
-                || Ok(KeywordSearchServer::new()),
+                move || Ok(KeywordSearchServer::new(enable_index_files)),
                 LocalSessionManager::default().into(),
                 Default::default(),
             );
@@ -94,7 +150,7 @@ async fn main() -> anyhow::Result<()> {
         TransportType::Sse => {
             let ct = SseServer::serve(args.socket_addr.parse()?)
                 .await?
-                .with_service(KeywordSearchServer::new);
+                .with_service(move || KeywordSearchServer::new(enable_index_files));
 
             tokio::signal::ctrl_c().await?;
             ct.cancel();
@@ -103,3 +159,55 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Index the files of a file or a directory, for the `index-files` subcommand
+async fn index_files(
+    conn_config: &ConnectionConfig,
+    path: PathBuf,
+    index: &str,
+    options: ChunkOptions,
+) -> anyhow::Result<()> {
+    // the paths in the titles of the chunks are relative to the files directory, or to the
+    // directory to index
+    let (root, path) = match &conn_config.files_dir {
+        Some(files_dir) => ingest::resolve_path(files_dir, &path.to_string_lossy())
+            .map_err(|e| anyhow::anyhow!(e))?,
+        None => {
+            let path = path
+                .canonicalize()
+                .map_err(|e| anyhow::anyhow!("Failed to read `{}`: {e}", path.display()))?;
+            let root = match path.is_dir() {
+                true => path.clone(),
+                false => path.parent().map(PathBuf::from).unwrap_or_default(),
+            };
+            (root, path)
+        }
+    };
+
+    tracing::info!("Reading files from {}", path.display());
+    let ingestion = ingest::read_files(&root, &path, options).map_err(|e| anyhow::anyhow!(e))?;
+    for skipped in &ingestion.skipped_files {
+        tracing::warn!("Skipped {}: {}", skipped.path, skipped.reason);
+    }
+    tracing::info!(
+        "Read {} files into {} chunks",
+        ingestion.files,
+        ingestion.documents.len()
+    );
+
+    let failed = create_index_from_chunks(conn_config, index, ingestion.documents)
+        .await
+        .map_err(|e| anyhow::anyhow!(e.message))?;
+    for result in &failed {
+        tracing::warn!(
+            "Failed to index a chunk: {}",
+            result.error.as_deref().unwrap_or_default()
+        );
+    }
+    tracing::info!(
+        "Indexed the files into {index}, {} chunks failed",
+        failed.len()
+    );
+
+    Ok(())
+}
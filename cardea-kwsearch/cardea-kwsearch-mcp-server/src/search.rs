@@ -1,8 +1,15 @@
-use crate::CONNECTION_CONFIG;
+use crate::{
+    CONNECTION_CONFIG,
+    ingest::{self, ChunkOptions, MAX_CHUNKS},
+};
 use cardea_kwsearch_mcp_common::{
-    CreateIndexRequest, CreateIndexResponse, SearchDocumentsRequest, SearchDocumentsResponse,
+    CreateIndexRequest, CreateIndexResponse, IndexFilesRequest, IndexFilesResponse,
+    KwDocumentResult, SearchDocumentsRequest, SearchDocumentsResponse,
+};
+use endpoints::rag::keyword_search::{
+    DocumentInput, IndexRequest, IndexResponse, QueryRequest, QueryResponse,
 };
-use endpoints::rag::keyword_search::{IndexRequest, IndexResponse, QueryRequest, QueryResponse};
+use reqwest::Method;
 use rmcp::{
     ErrorData as McpError, RoleServer, ServerHandler,
     handler::server::{router::tool::ToolRouter, tool::*},
@@ -10,7 +17,8 @@ use rmcp::{
     service::RequestContext,
     tool, tool_handler, tool_router,
 };
-use std::sync::OnceLock;
+use serde::{Serialize, de::DeserializeOwned};
+use std::{path::PathBuf, sync::OnceLock};
 use tracing::{error, info};
 
 const PROMPT_SEARCH_TOOL: &str = r#"
@@ -61,10 +69,13 @@ pub struct KeywordSearchServer {
 }
 #[tool_router]
 impl KeywordSearchServer {
-    pub fn new() -> Self {
-        Self {
-            tool_router: Self::tool_router(),
+    pub fn new(enable_index_files: bool) -> Self {
+        let mut tool_router = Self::tool_router();
+        if enable_index_files {
+            tool_router += Self::files_tool_router();
         }
+
+        Self { tool_router }
     }
 
     #[tool(description = "Create an index in the KeywordSearch database")]
@@ -74,22 +85,7 @@ impl KeywordSearchServer {
     ) -> Result<CallToolResult, McpError> {
         info!("Creating index in KeywordSearch database");
 
-        // get connection config
-        let conn_config = match CONNECTION_CONFIG.get() {
-            Some(connection_config) => {
-                let conn_config = connection_config.read().await;
-                conn_config.clone()
-            }
-            None => {
-                let error_message = "Connection config not found";
-                error!("{}", error_message);
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
-            }
-        };
+        let conn_config = get_connection_config().await?;
 
         // build url
         let base_url = conn_config.base_url.trim_end_matches('/');
@@ -135,22 +131,7 @@ impl KeywordSearchServer {
     ) -> Result<CallToolResult, McpError> {
         info!("Searching for documents in KeywordSearch database");
 
-        // get connection config
-        let conn_config = match CONNECTION_CONFIG.get() {
-            Some(connection_config) => {
-                let conn_config = connection_config.read().await;
-                conn_config.clone()
-            }
-            None => {
-                let error_message = "Connection config not found";
-                error!("{}", error_message);
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
-            }
-        };
+        let conn_config = get_connection_config().await?;
 
         // build url
         let base_url = conn_config.base_url.trim_end_matches('/');
@@ -190,6 +171,135 @@ impl KeywordSearchServer {
         Ok(CallToolResult::success(vec![content]))
     }
 }
+
+/// Tools that read local files. Only enabled with `--files-dir`.
+#[tool_router(router = files_tool_router)]
+impl KeywordSearchServer {
+    #[tool(
+        description = "Create an index from the text, Markdown, HTML and JSONL files of a directory of the server. The files are split into overlapping chunks by tokens, sentences or headings, with the title and the path of their file in their title"
+    )]
+    async fn index_files(
+        &self,
+        Parameters(IndexFilesRequest {
+            path,
+            index,
+            strategy,
+            chunk_size,
+            overlap,
+        }): Parameters<IndexFilesRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let Some(files_dir) = conn_config.files_dir.clone() else {
+            let error_message = "No files directory is configured";
+            error!("{}", error_message);
+            return Err(McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                error_message,
+                None,
+            ));
+        };
+        let options =
+            ChunkOptions::new(strategy, chunk_size, overlap).map_err(|error_message| {
+                error!("{}", error_message);
+                McpError::invalid_params(error_message, None)
+            })?;
+        let (root, path) = ingest::resolve_path(&files_dir, &path).map_err(|error_message| {
+            error!("{}", error_message);
+            McpError::invalid_params(error_message, None)
+        })?;
+
+        info!("Reading files from {}", path.display());
+
+        // reading and chunking the files may take a while
+        let ingestion =
+            tokio::task::spawn_blocking(move || ingest::read_files(&root, &path, options))
+                .await
+                .map_err(|e| {
+                    let error_message = format!("Failed to read files: {e}");
+                    error!("{}", error_message);
+                    McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+                })?
+                .map_err(|error_message| {
+                    error!("{}", error_message);
+                    McpError::invalid_params(error_message, None)
+                })?;
+
+        let chunks = ingestion.documents.len();
+        let failed_chunks =
+            create_index_from_chunks(&conn_config, &index, ingestion.documents).await?;
+
+        let response = IndexFilesResponse {
+            index,
+            files: ingestion.files,
+            chunks,
+            skipped_files: ingestion.skipped_files,
+            failed_chunks,
+        };
+
+        let content = Content::json(response)?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+}
+
+/// Create an index from the chunks of files. The kw-search-server can't add documents to an
+/// existing index, so all the chunks are sent in a single request, of at most `MAX_CHUNKS` chunks.
+/// The index searched by the server is only recreated with `--allow-recreate-index`. Returns the
+/// chunks that failed to be indexed.
+pub async fn create_index_from_chunks(
+    conn_config: &ConnectionConfig,
+    index: &str,
+    documents: Vec<DocumentInput>,
+) -> Result<Vec<KwDocumentResult>, McpError> {
+    if index == conn_config.index && !conn_config.allow_recreate_index {
+        let error_message = format!(
+            "The index `{index}` is the index searched by the server. Start the server with `--allow-recreate-index` to recreate it"
+        );
+        error!("{}", error_message);
+        return Err(McpError::invalid_params(error_message, None));
+    }
+    let total = documents.len();
+    if total == 0 {
+        let error_message = "No chunks to index: no supported file with text was found";
+        error!("{}", error_message);
+        return Err(McpError::invalid_params(error_message, None));
+    }
+    if total > MAX_CHUNKS {
+        let error_message = format!(
+            "The files are split into {total} chunks, more than the {MAX_CHUNKS} chunks an index can be created with. Index a smaller directory, or use a larger `chunk_size`"
+        );
+        error!("{}", error_message);
+        return Err(McpError::invalid_params(error_message, None));
+    }
+
+    info!("Creating {index} with {total} chunks");
+
+    let index_request = IndexRequest {
+        index: Some(index.to_string()),
+        documents,
+    };
+    let index_response: IndexResponse = send_request(
+        conn_config,
+        Method::POST,
+        "v1/index/create",
+        Some(&index_request),
+        "create index",
+    )
+    .await?;
+
+    let failed: Vec<KwDocumentResult> = index_response
+        .results
+        .into_iter()
+        .filter(|result| result.error.is_some())
+        .map(KwDocumentResult::from)
+        .collect();
+
+    info!("Indexed {total} chunks into {index}");
+
+    Ok(failed)
+}
+
 #[tool_handler]
 impl ServerHandler for KeywordSearchServer {
     fn get_info(&self) -> ServerInfo {
@@ -259,6 +369,76 @@ impl ServerHandler for KeywordSearchServer {
     }
 }
 
+async fn get_connection_config() -> Result<ConnectionConfig, McpError> {
+    match CONNECTION_CONFIG.get() {
+        Some(connection_config) => {
+            let conn_config = connection_config.read().await;
+            Ok(conn_config.clone())
+        }
+        None => {
+            let error_message = "Connection config not found";
+            error!("{}", error_message);
+            Err(McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                error_message,
+                None,
+            ))
+        }
+    }
+}
+
+/// Send a request to the kw-search-server, and return the parsed response body
+async fn send_request<T: DeserializeOwned>(
+    conn_config: &ConnectionConfig,
+    method: Method,
+    path: &str,
+    body: Option<&impl Serialize>,
+    operation: &str,
+) -> Result<T, McpError> {
+    // build url
+    let base_url = conn_config.base_url.trim_end_matches('/');
+    let url = format!("{base_url}/{path}");
+
+    let mut request = reqwest::Client::new().request(method, &url);
+    if let Some(body) = body {
+        request = request.json(body);
+    }
+
+    let response = request.send().await.map_err(|e| {
+        let error_message = format!("Failed to {operation}: {e}");
+
+        error!("{}", error_message);
+
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        // the kw-search-server returns a plain-text or JSON error message
+        let body = response.text().await.unwrap_or_default();
+        let error_message = match body.trim().is_empty() {
+            true => format!("Failed to {operation}: {status}"),
+            false => format!("Failed to {operation}: {status}. {}", body.trim()),
+        };
+
+        error!("{}", error_message);
+
+        return Err(McpError::new(
+            ErrorCode::INTERNAL_ERROR,
+            error_message,
+            None,
+        ));
+    }
+
+    response.json::<T>().await.map_err(|e| {
+        let error_message = format!("Failed to parse the response to {operation}: {e}");
+
+        error!("{}", error_message);
+
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct ConnectionConfig {
     pub base_url: String,
@@ -266,4 +446,8 @@ pub struct ConnectionConfig {
     pub api_key: Option<String>,
     pub index: String,
     pub limit: usize,
+    /// the directory that the `index_files` tool reads files from. `None` if the tool is disabled
+    pub files_dir: Option<PathBuf>,
+    /// whether the files can be indexed into `index`, which recreates the index searched by the server
+    pub allow_recreate_index: bool,
 }
@@ -1,7 +1,10 @@
 use rmcp::schemars;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json::{Map, Value};
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt};
+
+/// The description of the optional `collection` argument of the tools
+const COLLECTION_DESCRIPTION: &str = "The name of the collection. Defaults to the collection selected for the session or, if none, the default collection of the server";
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct CreateCollectionRequest {
@@ -25,6 +28,32 @@ impl From<rmcp::model::CallToolResult> for CreateCollectionResponse {
     }
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CollectionRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SelectCollectionRequest {
+    #[schemars(description = "The name of the collection to use for the rest of the session")]
+    pub collection: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct AllowedCollectionsResponse {
+    #[schemars(description = "The names of the collections that can be used")]
+    pub collections: Vec<String>,
+    #[schemars(description = "The collection used when no collection is given")]
+    pub current: String,
+}
+impl From<rmcp::model::CallToolResult> for AllowedCollectionsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<AllowedCollectionsResponse>(content).unwrap()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ListCollectionsResponse {
     #[schemars(description = "The list of collection names")]
@@ -95,8 +124,36 @@ impl From<rmcp::model::CallToolResult> for UpsertPointsResponse {
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
 pub struct SearchPointsRequest {
-    #[schemars(description = "The vector to search for")]
-    pub vector: Vec<f32>,
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(description = "The vector to search for. Either a dense or a sparse vector")]
+    pub vector: QueryVector,
+    #[schemars(
+        description = "The name of the vector to search in. Required for sparse vectors and for collections with named vectors"
+    )]
+    pub using: Option<String>,
+    #[schemars(description = "Only return the points that match the filter conditions")]
+    pub filter: Option<Filter>,
+    #[schemars(
+        description = "Maximum number of results to return. Defaults to the limit configured on the server"
+    )]
+    pub limit: Option<u64>,
+    #[schemars(description = "Number of results to skip. Used to paginate through the results")]
+    pub offset: Option<u64>,
+    #[schemars(
+        description = "Minimal score of the returned results. Defaults to the score threshold configured on the server"
+    )]
+    pub score_threshold: Option<f32>,
+    #[schemars(
+        description = "Whether to return the vectors of the points, or which named vectors to return. Defaults to false"
+    )]
+    pub with_vector: Option<WithVector>,
+    #[schemars(
+        description = "Whether to return the payloads of the points, or which payload fields to return. Defaults to true"
+    )]
+    pub with_payload: Option<WithPayload>,
+    #[schemars(description = "Additional parameters of the search")]
+    pub params: Option<SearchParams>,
 }
 
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
@@ -113,14 +170,774 @@ impl From<rmcp::model::CallToolResult> for SearchPointsResponse {
     }
 }
 
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SearchBatchRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(description = "The searches to perform")]
+    pub searches: Vec<SearchPointsRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SearchBatchResponse {
+    #[schemars(description = "The results of each search, in the order of the searches")]
+    pub result: Vec<Vec<ScoredPoint>>,
+    #[schemars(description = "The time it took to search the points")]
+    pub time: f64,
+}
+impl From<rmcp::model::CallToolResult> for SearchBatchResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<SearchBatchResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct HybridSearchRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(
+        description = "The sub-searches whose results are fused, e.g. one on a dense vector and one on a sparse vector"
+    )]
+    pub prefetch: Vec<Prefetch>,
+    #[schemars(
+        description = "How to fuse the results of the sub-searches. Defaults to `rrf` (Reciprocal Rank Fusion)"
+    )]
+    pub fusion: Option<Fusion>,
+    #[schemars(description = "Only return the points that match the filter conditions")]
+    pub filter: Option<Filter>,
+    #[schemars(
+        description = "Maximum number of results to return. Defaults to the limit configured on the server"
+    )]
+    pub limit: Option<u64>,
+    #[schemars(description = "Number of results to skip. Used to paginate through the results")]
+    pub offset: Option<u64>,
+    #[schemars(
+        description = "Minimal fused score of the returned results. Not applied by default, since fused scores are not on the same scale as similarity scores"
+    )]
+    pub score_threshold: Option<f32>,
+    #[schemars(
+        description = "Whether to return the vectors of the points, or which named vectors to return. Defaults to false"
+    )]
+    pub with_vector: Option<WithVector>,
+    #[schemars(
+        description = "Whether to return the payloads of the points, or which payload fields to return. Defaults to true"
+    )]
+    pub with_payload: Option<WithPayload>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Prefetch {
+    #[schemars(description = "The vector to search for. Either a dense or a sparse vector")]
+    pub query: QueryVector,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The name of the vector to search in")]
+    pub using: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Only consider the points that match the filter conditions")]
+    pub filter: Option<Filter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Maximum number of candidates of the sub-search. Defaults to the limit plus the offset of the hybrid search"
+    )]
+    pub limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Minimal score of the candidates of the sub-search")]
+    pub score_threshold: Option<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum Fusion {
+    /// Reciprocal Rank Fusion
+    Rrf,
+    /// Distribution-Based Score Fusion
+    Dbsf,
+}
+
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct ScoredPoint {
+    #[schemars(description = "The id of the point")]
+    pub id: PointId,
     #[schemars(description = "The score of the point")]
     pub score: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[schemars(description = "The payload of the point")]
-    pub payload: HashMap<String, Value>,
+    pub payload: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     #[schemars(description = "The vector of the point")]
-    pub vector: Vec<f64>,
+    pub vector: Option<VectorOutput>,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct RecommendPointsRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(
+        description = "Examples of what the results should be similar to. Each example is either a point id or a vector"
+    )]
+    pub positive: Vec<VectorExample>,
+    #[schemars(
+        description = "Examples of what the results should not be similar to. Each example is either a point id or a vector"
+    )]
+    pub negative: Option<Vec<VectorExample>>,
+    #[schemars(
+        description = "How to use the examples to find the results. Defaults to `average_vector`"
+    )]
+    pub strategy: Option<RecommendStrategy>,
+    #[schemars(
+        description = "The name of the vector to search in. Required for collections with named vectors"
+    )]
+    pub using: Option<String>,
+    #[schemars(description = "Only return the points that match the filter conditions")]
+    pub filter: Option<Filter>,
+    #[schemars(
+        description = "Maximum number of results to return. Defaults to the limit configured on the server"
+    )]
+    pub limit: Option<u64>,
+    #[schemars(description = "Number of results to skip. Used to paginate through the results")]
+    pub offset: Option<u64>,
+    #[schemars(
+        description = "Minimal score of the returned results. Defaults to the score threshold configured on the server"
+    )]
+    pub score_threshold: Option<f32>,
+    #[schemars(
+        description = "Whether to return the vectors of the points, or which named vectors to return. Defaults to false"
+    )]
+    pub with_vector: Option<WithVector>,
+    #[schemars(
+        description = "Whether to return the payloads of the points, or which payload fields to return. Defaults to true"
+    )]
+    pub with_payload: Option<WithPayload>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RecommendStrategy {
+    /// Search with the average of the positive examples minus the average of the negative examples
+    AverageVector,
+    /// Score each candidate against every example and keep the best positive or negative score
+    BestScore,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DiscoverPointsRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(
+        description = "The example to get close to. Either a point id or a vector. If not provided, only the context is used"
+    )]
+    pub target: Option<VectorExample>,
+    #[schemars(
+        description = "Pairs of positive and negative examples that constrain the search space"
+    )]
+    pub context: Vec<ContextPair>,
+    #[schemars(
+        description = "The name of the vector to search in. Required for collections with named vectors"
+    )]
+    pub using: Option<String>,
+    #[schemars(description = "Only return the points that match the filter conditions")]
+    pub filter: Option<Filter>,
+    #[schemars(
+        description = "Maximum number of results to return. Defaults to the limit configured on the server"
+    )]
+    pub limit: Option<u64>,
+    #[schemars(description = "Number of results to skip. Used to paginate through the results")]
+    pub offset: Option<u64>,
+    #[schemars(
+        description = "Whether to return the vectors of the points, or which named vectors to return. Defaults to false"
+    )]
+    pub with_vector: Option<WithVector>,
+    #[schemars(
+        description = "Whether to return the payloads of the points, or which payload fields to return. Defaults to true"
+    )]
+    pub with_payload: Option<WithPayload>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ContextPair {
+    #[schemars(description = "The example the results should be closer to")]
+    pub positive: VectorExample,
+    #[schemars(description = "The example the results should be further from")]
+    pub negative: VectorExample,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum VectorExample {
+    /// The id of a point in the collection
+    Id(PointId),
+    /// A raw vector
+    Vector(Vec<f32>),
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct ScrollPointsRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(description = "Only return the points that match the filter conditions")]
+    pub filter: Option<Filter>,
+    #[schemars(
+        description = "Maximum number of points to return. Defaults to the limit configured on the server"
+    )]
+    pub limit: Option<u64>,
+    #[schemars(
+        description = "The id of the point to start from. Use the `next_page_offset` of the previous response to get the next page"
+    )]
+    pub offset: Option<PointId>,
+    #[schemars(
+        description = "Whether to return the vectors of the points, or which named vectors to return. Defaults to false"
+    )]
+    pub with_vector: Option<WithVector>,
+    #[schemars(
+        description = "Whether to return the payloads of the points, or which payload fields to return. Defaults to true"
+    )]
+    pub with_payload: Option<WithPayload>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ScrollPointsResponse {
+    #[schemars(description = "The points of the current page")]
+    pub points: Vec<Record>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "The offset of the next page. Not present if there are no more points"
+    )]
+    pub next_page_offset: Option<PointId>,
+    #[schemars(description = "The time it took to scroll the points")]
+    pub time: f64,
+}
+impl From<rmcp::model::CallToolResult> for ScrollPointsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ScrollPointsResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetPointsRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(description = "The ids of the points to retrieve")]
+    pub ids: Vec<PointId>,
+    #[schemars(
+        description = "Whether to return the vectors of the points, or which named vectors to return. Defaults to false"
+    )]
+    pub with_vector: Option<WithVector>,
+    #[schemars(
+        description = "Whether to return the payloads of the points, or which payload fields to return. Defaults to true"
+    )]
+    pub with_payload: Option<WithPayload>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetPointsResponse {
+    #[schemars(description = "The retrieved points. Ids that do not exist are omitted")]
+    pub result: Vec<Record>,
+    #[schemars(description = "The time it took to retrieve the points")]
+    pub time: f64,
+}
+impl From<rmcp::model::CallToolResult> for GetPointsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<GetPointsResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct Record {
+    #[schemars(description = "The id of the point")]
+    pub id: PointId,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The payload of the point")]
+    pub payload: Option<HashMap<String, Value>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The vector of the point")]
+    pub vector: Option<VectorOutput>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum PointId {
+    /// An unsigned integer id
+    Num(u64),
+    /// A UUID id
+    Uuid(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum QueryVector {
+    /// A dense vector
+    Dense(Vec<f32>),
+    /// A sparse vector
+    Sparse(SparseVector),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SparseVector {
+    #[schemars(description = "The indices of the non-zero elements")]
+    pub indices: Vec<u32>,
+    #[schemars(description = "The values of the non-zero elements")]
+    pub values: Vec<f32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum VectorOutput {
+    /// The unnamed dense vector of the point
+    Single(Vec<f64>),
+    /// The named vectors of the point
+    Named(HashMap<String, NamedVectorOutput>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum NamedVectorOutput {
+    /// A dense vector
+    Dense(Vec<f64>),
+    /// A sparse vector
+    Sparse(SparseVector),
+    /// A multi-vector
+    MultiDense(Vec<Vec<f64>>),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum WithVector {
+    /// Return all vectors if `true`, none if `false`
+    Enable(bool),
+    /// Return only the given named vectors
+    Include(Vec<String>),
+}
+
+impl fmt::Display for PointId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointId::Num(id) => write!(f, "{id}"),
+            PointId::Uuid(id) => write!(f, "{id}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum WithPayload {
+    /// Return all payload fields if `true`, none if `false`
+    Enable(bool),
+    /// Return only the given payload fields
+    Include(Vec<String>),
+    /// Return the payload fields selected by `include` or `exclude`
+    Selector(PayloadSelector),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadSelector {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Only return these payload fields")]
+    pub include: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Return all payload fields except these")]
+    pub exclude: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SearchParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Size of the beam in the HNSW search. Larger values give more accurate results but make the search slower"
+    )]
+    pub hnsw_ef: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(
+        description = "Search without the approximation of the vector index. Gives exact results but may be very slow"
+    )]
+    pub exact: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Filter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "All of these conditions must match")]
+    pub must: Option<Vec<Condition>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "At least one of these conditions must match")]
+    pub should: Option<Vec<Condition>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "None of these conditions may match")]
+    pub must_not: Option<Vec<Condition>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum Condition {
+    /// Check the value of a payload field
+    Field(FieldCondition),
+    /// Check if a payload field is empty or missing
+    IsEmpty(IsEmptyCondition),
+    /// Check if a payload field is `null`
+    IsNull(IsNullCondition),
+    /// Check if the point id is one of the given ids
+    HasId(HasIdCondition),
+    /// A nested filter
+    Filter(Filter),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FieldCondition {
+    #[schemars(description = "The payload field to check, e.g. `city` or `country.name`")]
+    pub key: String,
+    #[serde(rename = "match", skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Match the value of the field")]
+    pub match_: Option<Match>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Check that the value of the field is in the range")]
+    pub range: Option<Range>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(untagged)]
+pub enum Match {
+    /// The field equals the given keyword, integer or boolean
+    Value { value: Value },
+    /// The field contains the given text
+    Text { text: String },
+    /// The field equals any of the given values
+    Any { any: Vec<Value> },
+    /// The field equals none of the given values
+    Except { except: Vec<Value> },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct Range {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Greater than. A number or an RFC 3339 datetime")]
+    pub gt: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Greater than or equal to. A number or an RFC 3339 datetime")]
+    pub gte: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Less than. A number or an RFC 3339 datetime")]
+    pub lt: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "Less than or equal to. A number or an RFC 3339 datetime")]
+    pub lte: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IsEmptyCondition {
+    #[schemars(description = "The payload field to check")]
+    pub is_empty: PayloadField,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct IsNullCondition {
+    #[schemars(description = "The payload field to check")]
+    pub is_null: PayloadField,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PayloadField {
+    #[schemars(description = "The name of the payload field")]
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct HasIdCondition {
+    #[schemars(description = "The ids of the points to match")]
+    pub has_id: Vec<PointId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SnapshotDescription {
+    #[schemars(description = "The name of the snapshot")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The time the snapshot was created")]
+    pub creation_time: Option<String>,
+    #[schemars(description = "The size of the snapshot in bytes")]
+    pub size: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The SHA256 checksum of the snapshot")]
+    pub checksum: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CreateSnapshotResponse {
+    #[schemars(description = "The created snapshot")]
+    pub result: SnapshotDescription,
+    #[schemars(description = "The time it took to create the snapshot")]
+    pub time: f64,
+}
+impl From<rmcp::model::CallToolResult> for CreateSnapshotResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<CreateSnapshotResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ListSnapshotsResponse {
+    #[schemars(description = "The snapshots of the collection")]
+    pub snapshots: Vec<SnapshotDescription>,
+    #[schemars(description = "The time it took to list the snapshots")]
+    pub time: f64,
+}
+impl From<rmcp::model::CallToolResult> for ListSnapshotsResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<ListSnapshotsResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct SnapshotRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(description = "The name of the snapshot")]
+    pub snapshot_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct SnapshotUrlResponse {
+    #[schemars(description = "The URL to download the snapshot from")]
+    pub url: String,
+    #[schemars(
+        description = "Whether the Qdrant database requires the API key in the `api-key` header to download the snapshot"
+    )]
+    pub requires_api_key: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct DeleteSnapshotResponse {
+    #[schemars(description = "Whether the snapshot was deleted")]
+    pub result: bool,
+    #[schemars(description = "The time it took to delete the snapshot")]
+    pub time: f64,
+}
+
+/// The envelope of the responses of the Qdrant REST API
+#[derive(Debug, Deserialize)]
+pub struct QdrantResponse<T> {
+    /// The result of the operation. Not present if the operation failed
+    pub result: Option<T>,
+    /// The status of the operation
+    pub status: QdrantStatus,
+    /// The time it took to perform the operation, in seconds
+    #[serde(default)]
+    pub time: f64,
+}
+impl<T: DeserializeOwned> QdrantResponse<T> {
+    /// Parse a response body, and return the result and the time of the operation
+    pub fn parse(body: &[u8]) -> Result<(T, f64), QdrantError> {
+        let response =
+            serde_json::from_slice::<QdrantResponse<T>>(body).map_err(QdrantError::Parse)?;
+
+        match (response.status, response.result) {
+            (QdrantStatus::Error { error }, _) => Err(QdrantError::Status(error)),
+            (QdrantStatus::Ok(_), Some(result)) => Ok((result, response.time)),
+            (QdrantStatus::Ok(_), None) => Err(QdrantError::MissingResult),
+        }
+    }
+}
+
+/// The status of a Qdrant response, either `"ok"` or `{"error": "..."}`
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum QdrantStatus {
+    Ok(String),
+    Error { error: String },
+}
+
+#[derive(Debug)]
+pub enum QdrantError {
+    /// The error reported by Qdrant in `status.error`
+    Status(String),
+    /// The response has no `result`
+    MissingResult,
+    /// The response does not match the expected schema
+    Parse(serde_json::Error),
+}
+impl fmt::Display for QdrantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QdrantError::Status(error) => write!(f, "{error}"),
+            QdrantError::MissingResult => write!(f, "The given key 'result' does not exist"),
+            QdrantError::Parse(e) => write!(f, "Invalid response: {e}"),
+        }
+    }
+}
+impl std::error::Error for QdrantError {}
+
+/// The `result` of a list collections request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CollectionsResult {
+    pub collections: Vec<CollectionDescription>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CollectionDescription {
+    pub name: String,
+}
+
+/// The `result` of a get collection request
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct CollectionInfo {
+    #[schemars(description = "The status of the collection: green, yellow, grey or red")]
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The approximate number of points in the collection")]
+    pub points_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The approximate number of indexed vectors in the collection")]
+    pub indexed_vectors_count: Option<u64>,
+    #[serde(default)]
+    #[schemars(description = "The number of segments of the collection")]
+    pub segments_count: u64,
+    #[schemars(
+        description = "The configuration of the collection, including the schema of its vectors"
+    )]
+    pub config: Value,
+    #[serde(default)]
+    #[schemars(description = "The indexed payload fields of the collection and their types")]
+    pub payload_schema: HashMap<String, PayloadIndexInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PayloadIndexInfo {
+    #[schemars(description = "The type of the indexed field")]
+    pub data_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The parameters of the index, e.g. the tokenizer of a text index")]
+    pub params: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(description = "The number of points indexed")]
+    pub points: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct GetCollectionInfoResponse {
+    #[schemars(description = "The information of the collection")]
+    pub result: CollectionInfo,
+    #[schemars(description = "The time it took to get the collection information")]
+    pub time: f64,
+}
+impl From<rmcp::model::CallToolResult> for GetCollectionInfoResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<GetCollectionInfoResponse>(content).unwrap()
+    }
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct CreatePayloadIndexRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(
+        description = "The name of the payload field to index, e.g. `city` or `country.name`"
+    )]
+    pub field_name: String,
+    #[schemars(description = "The type of the payload field")]
+    pub field_type: PayloadFieldType,
+    #[schemars(
+        description = "How to split the text into tokens. Only for `text` fields. Defaults to `word`"
+    )]
+    pub tokenizer: Option<TextTokenizer>,
+    #[schemars(description = "Minimal length of a token. Only for `text` fields")]
+    pub min_token_len: Option<u64>,
+    #[schemars(description = "Maximal length of a token. Only for `text` fields")]
+    pub max_token_len: Option<u64>,
+    #[schemars(
+        description = "Whether to lowercase the tokens. Only for `text` fields. Defaults to true"
+    )]
+    pub lowercase: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PayloadFieldType {
+    /// Exact string values, used by `match` conditions
+    Keyword,
+    /// Integer values, used by `match` and `range` conditions
+    Integer,
+    /// Float values, used by `range` conditions
+    Float,
+    /// Boolean values
+    Bool,
+    /// Geo points
+    Geo,
+    /// RFC 3339 datetimes, used by `range` conditions
+    Datetime,
+    /// Full text, used by `match.text` conditions
+    Text,
+    /// UUID strings
+    Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TextTokenizer {
+    /// Split the text into words by spaces, punctuation and special characters
+    Word,
+    /// Split the text into words by spaces
+    Whitespace,
+    /// Split the text into words and index all the prefixes of each word
+    Prefix,
+    /// Split the text with language-aware rules, e.g. for Chinese and Japanese
+    Multilingual,
+}
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct DeletePayloadIndexRequest {
+    #[schemars(description = COLLECTION_DESCRIPTION)]
+    pub collection: Option<String>,
+    #[schemars(description = "The name of the indexed payload field")]
+    pub field_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct PayloadIndexResponse {
+    #[schemars(
+        description = "The status of the operation. Allowed values: 'acknowledged', 'completed'"
+    )]
+    pub status: String,
+    #[schemars(description = "The time it took to perform the operation")]
+    pub time: f64,
+}
+impl From<rmcp::model::CallToolResult> for PayloadIndexResponse {
+    fn from(result: rmcp::model::CallToolResult) -> Self {
+        let content = result.content[0].as_text().unwrap().text.as_ref();
+        serde_json::from_str::<PayloadIndexResponse>(content).unwrap()
+    }
+}
+
+/// The `result` of an operation that updates a collection
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateResult {
+    #[serde(default)]
+    pub operation_id: Option<u64>,
+    pub status: String,
+}
+
+/// The `result` of a scroll request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScrollResult {
+    pub points: Vec<Record>,
+    pub next_page_offset: Option<PointId>,
+}
+
+/// The `result` of a query request
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryResult {
+    pub points: Vec<ScoredPoint>,
 }
 
 #[derive(Debug, Clone)]
@@ -128,3 +945,129 @@ pub struct ConnectionConfig {
     pub base_url: String,
     pub api_key: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_search_result() {
+        let body = br#"{
+            "result": [
+                {"id": 42, "version": 3, "score": 0.87, "payload": {"city": "Berlin"}, "vector": null},
+                {"id": "5c56c793-69f3-4fbf-87e6-c4bf54c28c26", "version": 1, "score": 0.65}
+            ],
+            "status": "ok",
+            "time": 0.002
+        }"#;
+
+        let (points, time) = QdrantResponse::<Vec<ScoredPoint>>::parse(body).unwrap();
+        assert_eq!(time, 0.002);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].id, PointId::Num(42));
+        assert_eq!(points[0].score, 0.87);
+        assert_eq!(points[0].payload.as_ref().unwrap()["city"], "Berlin");
+        assert!(points[0].vector.is_none());
+        assert_eq!(
+            points[1].id,
+            PointId::Uuid("5c56c793-69f3-4fbf-87e6-c4bf54c28c26".to_string())
+        );
+        assert!(points[1].payload.is_none());
+    }
+
+    #[test]
+    fn parse_scroll_result_with_next_page_offset() {
+        let body = br#"{
+            "result": {
+                "points": [
+                    {"id": 1, "payload": {"color": "red"}},
+                    {"id": 2, "payload": {"color": "green"}}
+                ],
+                "next_page_offset": 3
+            },
+            "status": "ok",
+            "time": 0.0005
+        }"#;
+
+        let (page, _) = QdrantResponse::<ScrollResult>::parse(body).unwrap();
+        assert_eq!(page.points.len(), 2);
+        assert_eq!(page.points[1].id, PointId::Num(2));
+        assert_eq!(page.next_page_offset, Some(PointId::Num(3)));
+
+        let body = br#"{"result": {"points": [], "next_page_offset": null}, "status": "ok", "time": 0.0001}"#;
+        let (page, _) = QdrantResponse::<ScrollResult>::parse(body).unwrap();
+        assert!(page.points.is_empty());
+        assert_eq!(page.next_page_offset, None);
+    }
+
+    #[test]
+    fn parse_point_without_payload_and_vector() {
+        let body = br#"{"result": [{"id": 7}], "status": "ok", "time": 0.0003}"#;
+
+        let (records, _) = QdrantResponse::<Vec<Record>>::parse(body).unwrap();
+        assert_eq!(records[0].id, PointId::Num(7));
+        assert!(records[0].payload.is_none());
+        assert!(records[0].vector.is_none());
+    }
+
+    #[test]
+    fn parse_named_vectors() {
+        let body = br#"{
+            "result": [{
+                "id": 7,
+                "payload": {},
+                "vector": {
+                    "text": [0.1, 0.2, 0.3],
+                    "keywords": {"indices": [1, 42], "values": [0.5, 0.25]},
+                    "colbert": [[0.1, 0.2], [0.3, 0.4]]
+                }
+            }],
+            "status": "ok",
+            "time": 0.0003
+        }"#;
+
+        let (records, _) = QdrantResponse::<Vec<Record>>::parse(body).unwrap();
+        let Some(VectorOutput::Named(vectors)) = &records[0].vector else {
+            panic!("expected named vectors, got {:?}", records[0].vector);
+        };
+        assert!(matches!(&vectors["text"], NamedVectorOutput::Dense(v) if v.len() == 3));
+        assert!(
+            matches!(&vectors["keywords"], NamedVectorOutput::Sparse(v) if v.indices == [1, 42])
+        );
+        assert!(matches!(&vectors["colbert"], NamedVectorOutput::MultiDense(v) if v.len() == 2));
+    }
+
+    #[test]
+    fn parse_error_status() {
+        let body = br#"{
+            "status": {"error": "Not found: Collection `missing` doesn't exist!"},
+            "time": 0.0001
+        }"#;
+
+        match QdrantResponse::<Vec<ScoredPoint>>::parse(body) {
+            Err(QdrantError::Status(error)) => {
+                assert_eq!(error, "Not found: Collection `missing` doesn't exist!")
+            }
+            other => panic!("expected a status error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn collection_description_in_schema() {
+        let schema = serde_json::to_value(schemars::schema_for!(CollectionRequest)).unwrap();
+        assert_eq!(
+            schema["properties"]["collection"]["description"],
+            COLLECTION_DESCRIPTION
+        );
+    }
+
+    #[test]
+    fn parse_missing_result() {
+        let body = br#"{"status": "ok", "time": 0.0001}"#;
+
+        assert!(matches!(
+            QdrantResponse::<Vec<ScoredPoint>>::parse(body),
+            Err(QdrantError::MissingResult)
+        ));
+    }
+}
@@ -28,9 +28,12 @@ struct Args {
     /// Transport type to use
     #[arg(short, long, value_enum, default_value = "stream-http")]
     transport: TransportType,
-    /// Name of the collection to search
+    /// Name of the collection to search when no collection is given to a tool
     #[arg(long, required = true)]
     collection: String,
+    /// Names of the other collections that the tools are allowed to use, separated by commas
+    #[arg(long, value_delimiter = ',')]
+    allowed_collections: Vec<String>,
     /// Maximum number of results to return
     #[arg(long, default_value = "10")]
     limit: u64,
@@ -43,6 +46,9 @@ struct Args {
         default_value = "Perform vector search with the input vector. Return a tool call that invokes the vector search tool.\n\nThe input vector is: [0.0,0.0,0.0,0.0]"
     )]
     search_tool_prompt: String,
+    /// Enable the admin tools, e.g. snapshot management
+    #[arg(long)]
+    enable_admin_tools: bool,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -69,10 +75,20 @@ async fn main() -> anyhow::Result<()> {
     // parse api key
     let api_key = std::env::var("QDRANT_API_KEY").ok();
 
+    // the default collection is always allowed
+    let mut allowed_collections = vec![args.collection.clone()];
+    for collection in args.allowed_collections {
+        if !allowed_collections.contains(&collection) {
+            allowed_collections.push(collection);
+        }
+    }
+    tracing::info!("Allowed collections: {}", allowed_collections.join(", "));
+
     let connection_config = ConnectionConfig {
         base_url,
         api_key,
         collection: args.collection,
+        allowed_collections,
         limit: args.limit,
         score_threshold: args.score_threshold,
     };
@@ -84,12 +100,17 @@ async fn main() -> anyhow::Result<()> {
     // Set the search tool prompt from CLI
     set_search_tool_prompt(args.search_tool_prompt);
 
+    let enable_admin_tools = args.enable_admin_tools;
+    if enable_admin_tools {
+        tracing::info!("Admin tools are enabled");
+    }
+
     tracing::info!("Starting Cardea Qdrant MCP server on {}", args.socket_addr);
 
     match args.transport {
         TransportType::StreamHttp => {
             let service = StreamableHttpService::new(
-                || Ok(QdrantServer::new()),
+                move || Ok(QdrantServer::new(enable_admin_tools)),
                 LocalSessionManager::default().into(),
                 Default::default(),
             );
@@ -103,16 +124,19 @@ async fn main() -> anyhow::Result<()> {
         TransportType::Sse => {
             let ct = SseServer::serve(args.socket_addr.parse()?)
                 .await?
-                .with_service(QdrantServer::new);
+                .with_service(move || QdrantServer::new(enable_admin_tools));
 
             tokio::signal::ctrl_c().await?;
             ct.cancel();
         }
         TransportType::Stdio => {
             // Create an instance of our counter router
-            let service = QdrantServer::new().serve(stdio()).await.inspect_err(|e| {
-                tracing::error!("serving error: {:?}", e);
-            })?;
+            let service = QdrantServer::new(enable_admin_tools)
+                .serve(stdio())
+                .await
+                .inspect_err(|e| {
+                    tracing::error!("serving error: {:?}", e);
+                })?;
 
             service.waiting().await?;
         }
@@ -125,7 +149,10 @@ async fn main() -> anyhow::Result<()> {
 pub struct ConnectionConfig {
     pub base_url: String,
     pub api_key: Option<String>,
+    /// the default collection
     pub collection: String,
+    /// the collections that the tools are allowed to use, including the default collection
+    pub allowed_collections: Vec<String>,
     pub limit: u64,
     pub score_threshold: f32,
 }
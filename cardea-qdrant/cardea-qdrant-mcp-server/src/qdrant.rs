@@ -1,5 +1,6 @@
-use crate::CONNECTION_CONFIG;
+use crate::{CONNECTION_CONFIG, ConnectionConfig};
 use cardea_qdrant_mcp_common::*;
+use reqwest::Method;
 use rmcp::{
     ErrorData as McpError, RoleServer, ServerHandler,
     handler::server::{router::tool::ToolRouter, tool::*},
@@ -7,10 +8,14 @@ use rmcp::{
     service::RequestContext,
     tool, tool_handler, tool_router,
 };
+use serde::de::DeserializeOwned;
 use serde_json::{Value, json};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
+use tokio::sync::RwLock;
 use tracing::error;
 
+const RESOURCE_URI_PREFIX: &str = "qdrant://collections/";
+
 static SEARCH_TOOL_PROMPT: OnceLock<String> = OnceLock::new();
 
 pub fn set_search_tool_prompt(prompt: String) {
@@ -19,170 +24,754 @@ pub fn set_search_tool_prompt(prompt: String) {
 
 #[derive(Debug, Clone)]
 pub struct QdrantServer {
+    /// The collection selected with the `select_collection` tool for the current session
+    selected_collection: Arc<RwLock<Option<String>>>,
     tool_router: ToolRouter<Self>,
 }
 #[tool_router]
 impl QdrantServer {
-    pub fn new() -> Self {
+    pub fn new(enable_admin_tools: bool) -> Self {
+        let tool_router = match enable_admin_tools {
+            true => Self::tool_router() + Self::admin_tool_router(),
+            false => Self::tool_router(),
+        };
+
         Self {
-            tool_router: Self::tool_router(),
+            selected_collection: Arc::new(RwLock::new(None)),
+            tool_router,
         }
     }
 
+    #[tool(
+        description = "List the Qdrant collections that can be used, and the collection used when no collection is given"
+    )]
+    async fn list_collections(&self) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let current = self.resolve_collection(None, &conn_config).await?;
+
+        let content = Content::json(AllowedCollectionsResponse {
+            collections: conn_config.allowed_collections,
+            current,
+        })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Select the Qdrant collection to use for the rest of the session when no collection is given"
+    )]
+    async fn select_collection(
+        &self,
+        Parameters(SelectCollectionRequest { collection }): Parameters<SelectCollectionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self
+            .resolve_collection(Some(collection), &conn_config)
+            .await?;
+
+        *self.selected_collection.write().await = Some(collection.clone());
+
+        let content = Content::json(AllowedCollectionsResponse {
+            collections: conn_config.allowed_collections,
+            current: collection,
+        })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
     #[tool(description = "Perform vector search in the Qdrant database")]
     async fn search(
         &self,
-        Parameters(SearchPointsRequest { vector }): Parameters<SearchPointsRequest>,
+        Parameters(mut request): Parameters<SearchPointsRequest>,
     ) -> Result<CallToolResult, McpError> {
-        // get connection config
-        let conn_config = match CONNECTION_CONFIG.get() {
-            Some(connection_config) => {
-                let conn_config = connection_config.read().await;
-                conn_config.clone()
-            }
-            None => {
-                let error_message = "Connection config not found";
-                error!("{}", error_message);
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
-            }
-        };
+        let conn_config = get_connection_config().await?;
+        let collection = self
+            .resolve_collection(request.collection.take(), &conn_config)
+            .await?;
 
-        // build url
-        let base_url = conn_config.base_url.trim_end_matches('/');
-        let url = format!(
-            "{}/collections/{}/points/search",
-            base_url, conn_config.collection
-        );
+        let params = build_search_params(request, &conn_config);
+
+        let path = format!("collections/{}/points/search", collection);
+        let (result, time) = send_request(
+            &conn_config,
+            Method::POST,
+            &path,
+            Some(params),
+            "search points",
+        )
+        .await?;
+
+        let content = Content::json(SearchPointsResponse { result, time })?;
 
-        // get api key
-        let api_key = conn_config.api_key;
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Perform several vector searches in the Qdrant database with a single request"
+    )]
+    async fn search_batch(
+        &self,
+        Parameters(SearchBatchRequest {
+            collection,
+            searches,
+        }): Parameters<SearchBatchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        if searches.iter().any(|search| search.collection.is_some()) {
+            let error_message =
+                "The collection of a batch must be given on the batch instead of on each search";
+            error!("{}", error_message);
+            return Err(McpError::invalid_params(error_message, None));
+        }
+
+        // build params
+        let searches = searches
+            .into_iter()
+            .map(|request| build_search_params(request, &conn_config))
+            .collect::<Vec<_>>();
+        let params = json!({ "searches": searches });
+
+        let path = format!("collections/{}/points/search/batch", collection);
+        let (result, time) = send_request(
+            &conn_config,
+            Method::POST,
+            &path,
+            Some(params),
+            "search points in batch",
+        )
+        .await?;
+
+        let content = Content::json(SearchBatchResponse { result, time })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Perform a hybrid search in the Qdrant database, which runs several sub-searches (e.g. on dense and sparse vectors) and fuses their results"
+    )]
+    async fn hybrid_search(
+        &self,
+        Parameters(HybridSearchRequest {
+            collection,
+            prefetch,
+            fusion,
+            filter,
+            limit,
+            offset,
+            score_threshold,
+            with_vector,
+            with_payload,
+        }): Parameters<HybridSearchRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        let limit = limit.unwrap_or(conn_config.limit);
+        // the fusion must see the candidates of the skipped hits too, to return a full page
+        let prefetch_limit = limit.saturating_add(offset.unwrap_or(0));
+        let prefetch = prefetch
+            .into_iter()
+            .map(|prefetch| Prefetch {
+                limit: prefetch.limit.or(Some(prefetch_limit)),
+                ..prefetch
+            })
+            .collect::<Vec<_>>();
+
+        // build params
+        let mut params = json!({
+            "prefetch": prefetch,
+            "query": { "fusion": fusion.unwrap_or(Fusion::Rrf) },
+            "limit": limit,
+            "with_payload": with_payload.unwrap_or(WithPayload::Enable(true)),
+            "with_vector": with_vector.unwrap_or(WithVector::Enable(false)),
+        });
+        if let Some(filter) = filter {
+            params["filter"] = json!(filter);
+        }
+        if let Some(offset) = offset {
+            params["offset"] = json!(offset);
+        }
+        if let Some(score_threshold) = score_threshold {
+            params["score_threshold"] = json!(score_threshold);
+        }
+
+        let path = format!("collections/{}/points/query", collection);
+        let (result, time): (QueryResult, f64) = send_request(
+            &conn_config,
+            Method::POST,
+            &path,
+            Some(params),
+            "query points",
+        )
+        .await?;
+
+        let content = Content::json(SearchPointsResponse {
+            result: result.points,
+            time,
+        })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Recommend points that are similar to the positive examples and dissimilar to the negative examples. The examples are point ids or vectors"
+    )]
+    async fn recommend(
+        &self,
+        Parameters(RecommendPointsRequest {
+            collection,
+            positive,
+            negative,
+            strategy,
+            using,
+            filter,
+            limit,
+            offset,
+            score_threshold,
+            with_vector,
+            with_payload,
+        }): Parameters<RecommendPointsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        // build params
+        let mut params = json!({
+            "positive": positive,
+            "negative": negative.unwrap_or_default(),
+            "limit": limit.unwrap_or(conn_config.limit),
+            "with_payload": with_payload.unwrap_or(WithPayload::Enable(true)),
+            "with_vector": with_vector.unwrap_or(WithVector::Enable(false)),
+            "score_threshold": score_threshold.unwrap_or(conn_config.score_threshold),
+        });
+        if let Some(strategy) = strategy {
+            params["strategy"] = json!(strategy);
+        }
+        if let Some(using) = using {
+            params["using"] = json!(using);
+        }
+        if let Some(filter) = filter {
+            params["filter"] = json!(filter);
+        }
+        if let Some(offset) = offset {
+            params["offset"] = json!(offset);
+        }
+
+        let path = format!("collections/{}/points/recommend", collection);
+        let (result, time) = send_request(
+            &conn_config,
+            Method::POST,
+            &path,
+            Some(params),
+            "recommend points",
+        )
+        .await?;
+
+        let content = Content::json(SearchPointsResponse { result, time })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Discover points that are close to the target while staying on the positive side of each context pair. The examples are point ids or vectors"
+    )]
+    async fn discover(
+        &self,
+        Parameters(DiscoverPointsRequest {
+            collection,
+            target,
+            context,
+            using,
+            filter,
+            limit,
+            offset,
+            with_vector,
+            with_payload,
+        }): Parameters<DiscoverPointsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        // build params
+        let mut params = json!({
+            "context": context,
+            "limit": limit.unwrap_or(conn_config.limit),
+            "with_payload": with_payload.unwrap_or(WithPayload::Enable(true)),
+            "with_vector": with_vector.unwrap_or(WithVector::Enable(false)),
+        });
+        if let Some(target) = target {
+            params["target"] = json!(target);
+        }
+        if let Some(using) = using {
+            params["using"] = json!(using);
+        }
+        if let Some(filter) = filter {
+            params["filter"] = json!(filter);
+        }
+        if let Some(offset) = offset {
+            params["offset"] = json!(offset);
+        }
+
+        let path = format!("collections/{}/points/discover", collection);
+        let (result, time) = send_request(
+            &conn_config,
+            Method::POST,
+            &path,
+            Some(params),
+            "discover points",
+        )
+        .await?;
+
+        let content = Content::json(SearchPointsResponse { result, time })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Scroll through the points of the Qdrant collection page by page, optionally filtered by payload conditions"
+    )]
+    async fn scroll_points(
+        &self,
+        Parameters(ScrollPointsRequest {
+            collection,
+            filter,
+            limit,
+            offset,
+            with_vector,
+            with_payload,
+        }): Parameters<ScrollPointsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        // build params
+        let mut params = json!({
+            "limit": limit.unwrap_or(conn_config.limit),
+            "with_payload": with_payload.unwrap_or(WithPayload::Enable(true)),
+            "with_vector": with_vector.unwrap_or(WithVector::Enable(false)),
+        });
+        if let Some(filter) = filter {
+            params["filter"] = json!(filter);
+        }
+        if let Some(offset) = offset {
+            params["offset"] = json!(offset);
+        }
+
+        let path = format!("collections/{}/points/scroll", collection);
+        let (page, time): (ScrollResult, f64) = send_request(
+            &conn_config,
+            Method::POST,
+            &path,
+            Some(params),
+            "scroll points",
+        )
+        .await?;
+
+        let content = Content::json(ScrollPointsResponse {
+            points: page.points,
+            next_page_offset: page.next_page_offset,
+            time,
+        })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Retrieve points from the Qdrant collection by their ids")]
+    async fn get_points(
+        &self,
+        Parameters(GetPointsRequest {
+            collection,
+            ids,
+            with_vector,
+            with_payload,
+        }): Parameters<GetPointsRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
 
         // build params
         let params = json!({
-            "vector": vector,
-            "limit": conn_config.limit,
-            "with_payload": true,
-            "with_vector": true,
-            "score_threshold": conn_config.score_threshold,
+            "ids": ids,
+            "with_payload": with_payload.unwrap_or(WithPayload::Enable(true)),
+            "with_vector": with_vector.unwrap_or(WithVector::Enable(false)),
         });
 
-        let client = reqwest::Client::new();
-        let result = match api_key {
-            Some(api_key) => {
-                client
-                    .post(&url)
-                    .header("api-key", api_key)
-                    .header("Content-Type", "application/json")
-                    .json(&params)
-                    .send()
-                    .await
+        let path = format!("collections/{}/points", collection);
+        let (result, time) = send_request(
+            &conn_config,
+            Method::POST,
+            &path,
+            Some(params),
+            "get points",
+        )
+        .await?;
+
+        let content = Content::json(GetPointsResponse { result, time })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Get the information of the Qdrant collection, including its point count, vector config and indexed payload fields"
+    )]
+    async fn get_collection_info(
+        &self,
+        Parameters(CollectionRequest { collection }): Parameters<CollectionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        let path = format!("collections/{}", collection);
+        let (result, time) =
+            send_request(&conn_config, Method::GET, &path, None, "get collection").await?;
+
+        let content = Content::json(GetCollectionInfoResponse { result, time })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(
+        description = "Create an index on a payload field of the Qdrant collection. Filtering on indexed fields is much faster"
+    )]
+    async fn create_payload_index(
+        &self,
+        Parameters(CreatePayloadIndexRequest {
+            collection,
+            field_name,
+            field_type,
+            tokenizer,
+            min_token_len,
+            max_token_len,
+            lowercase,
+        }): Parameters<CreatePayloadIndexRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let has_text_options = tokenizer.is_some()
+            || min_token_len.is_some()
+            || max_token_len.is_some()
+            || lowercase.is_some();
+
+        // a plain type name, or the index params for a text field with tokenizer options
+        let field_schema = match (field_type, has_text_options) {
+            (field_type, false) => json!(field_type),
+            (PayloadFieldType::Text, true) => {
+                let mut field_schema = json!({ "type": PayloadFieldType::Text });
+                if let Some(tokenizer) = tokenizer {
+                    field_schema["tokenizer"] = json!(tokenizer);
+                }
+                if let Some(min_token_len) = min_token_len {
+                    field_schema["min_token_len"] = json!(min_token_len);
+                }
+                if let Some(max_token_len) = max_token_len {
+                    field_schema["max_token_len"] = json!(max_token_len);
+                }
+                if let Some(lowercase) = lowercase {
+                    field_schema["lowercase"] = json!(lowercase);
+                }
+                field_schema
             }
-            None => {
-                client
-                    .post(&url)
-                    .header("Content-Type", "application/json")
-                    .json(&params)
-                    .send()
-                    .await
+            (_, true) => {
+                let error_message = "The tokenizer options are only allowed for `text` fields";
+                error!("{}", error_message);
+                return Err(McpError::invalid_params(error_message, None));
             }
         };
 
-        let response = match result {
-            Ok(response) => response,
-            Err(e) => {
-                let error_message = format!("Failed to search points: {e}");
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
 
-                error!("{}", error_message);
+        let params = json!({
+            "field_name": field_name,
+            "field_schema": field_schema,
+        });
 
-                return Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ));
-            }
-        };
+        let path = format!("collections/{}/index?wait=true", collection);
+        let (result, time): (UpdateResult, f64) = send_request(
+            &conn_config,
+            Method::PUT,
+            &path,
+            Some(params),
+            "create payload index",
+        )
+        .await?;
 
-        match response.status().is_success() {
-            true => match response.json::<Value>().await {
-                Ok(json) => match json.get("result") {
-                    Some(result) => {
-                        let scored_points = result
-                            .as_array()
-                            .unwrap()
-                            .iter()
-                            .map(|v| ScoredPoint {
-                                score: v.get("score").unwrap().as_f64().unwrap(),
-                                payload: v
-                                    .get("payload")
-                                    .unwrap()
-                                    .as_object()
-                                    .unwrap()
-                                    .to_owned()
-                                    .into_iter()
-                                    .map(|(k, v)| (k.to_string(), v.clone()))
-                                    .collect(),
-                                vector: v
-                                    .get("vector")
-                                    .unwrap()
-                                    .as_array()
-                                    .unwrap()
-                                    .to_owned()
-                                    .iter()
-                                    .map(|v| v.as_f64().unwrap())
-                                    .collect::<Vec<f64>>(),
-                            })
-                            .collect();
-
-                        let content = Content::json(SearchPointsResponse {
-                            result: scored_points,
-                            time: json.get("time").unwrap().as_f64().unwrap(),
-                        })?;
-
-                        Ok(CallToolResult::success(vec![content]))
-                    }
-                    None => {
-                        let error_message =
-                            "Failed to search points. The given key 'result' does not exist.";
-
-                        error!("{}", error_message);
-
-                        Err(McpError::new(
-                            ErrorCode::INTERNAL_ERROR,
-                            error_message,
-                            None,
-                        ))
-                    }
-                },
-                Err(e) => {
-                    let error_message = format!("Failed to search points: {e}");
-
-                    error!("{}", error_message);
-
-                    Err(McpError::new(
-                        ErrorCode::INTERNAL_ERROR,
-                        error_message,
-                        None,
-                    ))
-                }
+        let content = Content::json(PayloadIndexResponse {
+            status: result.status,
+            time,
+        })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Delete the index on a payload field of the Qdrant collection")]
+    async fn delete_payload_index(
+        &self,
+        Parameters(DeletePayloadIndexRequest {
+            collection,
+            field_name,
+        }): Parameters<DeletePayloadIndexRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        let field_name = encode_path_segment(&field_name, "field name")?;
+
+        let path = format!("collections/{}/index/{field_name}?wait=true", collection);
+        let (result, time): (UpdateResult, f64) = send_request(
+            &conn_config,
+            Method::DELETE,
+            &path,
+            None,
+            "delete payload index",
+        )
+        .await?;
+
+        let content = Content::json(PayloadIndexResponse {
+            status: result.status,
+            time,
+        })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    /// Resolve the collection to use: the given collection, or the collection selected for the
+    /// session, or the default collection. The collection must be in the allow-list.
+    async fn resolve_collection(
+        &self,
+        collection: Option<String>,
+        conn_config: &ConnectionConfig,
+    ) -> Result<String, McpError> {
+        let collection = match collection {
+            Some(collection) => collection,
+            None => match self.selected_collection.read().await.as_ref() {
+                Some(collection) => collection.clone(),
+                None => conn_config.collection.clone(),
             },
-            false => {
-                let error_message = format!("Failed to search points: {}", response.status());
+        };
 
+        match conn_config.allowed_collections.contains(&collection) {
+            true => Ok(collection),
+            false => {
+                let error_message = format!(
+                    "The collection `{collection}` is not allowed. Allowed collections: {}",
+                    conn_config.allowed_collections.join(", ")
+                );
                 error!("{}", error_message);
-
-                Err(McpError::new(
-                    ErrorCode::INTERNAL_ERROR,
-                    error_message,
-                    None,
-                ))
+                Err(McpError::invalid_params(error_message, None))
             }
         }
     }
 }
+
+/// Build the body of a search request, falling back to the connection config for the unset options
+fn build_search_params(
+    SearchPointsRequest {
+        collection: _,
+        vector,
+        using,
+        filter,
+        limit,
+        offset,
+        score_threshold,
+        with_vector,
+        with_payload,
+        params: search_params,
+    }: SearchPointsRequest,
+    conn_config: &ConnectionConfig,
+) -> Value {
+    // a named vector is passed as `{"name": ..., "vector": ...}`
+    let vector = match using {
+        Some(name) => json!({ "name": name, "vector": vector }),
+        None => json!(vector),
+    };
+
+    let mut params = json!({
+        "vector": vector,
+        "limit": limit.unwrap_or(conn_config.limit),
+        "with_payload": with_payload.unwrap_or(WithPayload::Enable(true)),
+        "with_vector": with_vector.unwrap_or(WithVector::Enable(false)),
+        "score_threshold": score_threshold.unwrap_or(conn_config.score_threshold),
+    });
+    if let Some(filter) = filter {
+        params["filter"] = json!(filter);
+    }
+    if let Some(offset) = offset {
+        params["offset"] = json!(offset);
+    }
+    if let Some(search_params) = search_params {
+        params["params"] = json!(search_params);
+    }
+
+    params
+}
+
+/// Tools for maintaining the Qdrant database. Only available if the server is started with
+/// `--enable-admin-tools`.
+#[tool_router(router = admin_tool_router)]
+impl QdrantServer {
+    #[tool(
+        description = "Create a snapshot of the Qdrant collection. Take a snapshot before bulk changes such as re-indexing"
+    )]
+    async fn create_snapshot(
+        &self,
+        Parameters(CollectionRequest { collection }): Parameters<CollectionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        let path = format!("collections/{}/snapshots?wait=true", collection);
+        let (result, time) =
+            send_request(&conn_config, Method::POST, &path, None, "create snapshot").await?;
+
+        let content = Content::json(CreateSnapshotResponse { result, time })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "List the snapshots of the Qdrant collection")]
+    async fn list_snapshots(
+        &self,
+        Parameters(CollectionRequest { collection }): Parameters<CollectionRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        let path = format!("collections/{}/snapshots", collection);
+        let (snapshots, time) =
+            send_request(&conn_config, Method::GET, &path, None, "list snapshots").await?;
+
+        let content = Content::json(ListSnapshotsResponse { snapshots, time })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Get the URL to download a snapshot of the Qdrant collection from")]
+    async fn get_snapshot_url(
+        &self,
+        Parameters(SnapshotRequest {
+            collection,
+            snapshot_name,
+        }): Parameters<SnapshotRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        let snapshot_name = encode_path_segment(&snapshot_name, "snapshot name")?;
+
+        let base_url = conn_config.base_url.trim_end_matches('/');
+        let url = format!(
+            "{base_url}/collections/{}/snapshots/{snapshot_name}",
+            collection
+        );
+
+        let content = Content::json(SnapshotUrlResponse {
+            url,
+            requires_api_key: conn_config.api_key.is_some(),
+        })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+
+    #[tool(description = "Delete a snapshot of the Qdrant collection")]
+    async fn delete_snapshot(
+        &self,
+        Parameters(SnapshotRequest {
+            collection,
+            snapshot_name,
+        }): Parameters<SnapshotRequest>,
+    ) -> Result<CallToolResult, McpError> {
+        let conn_config = get_connection_config().await?;
+        let collection = self.resolve_collection(collection, &conn_config).await?;
+
+        let snapshot_name = encode_path_segment(&snapshot_name, "snapshot name")?;
+
+        let path = format!(
+            "collections/{}/snapshots/{snapshot_name}?wait=true",
+            collection
+        );
+        let (result, time) =
+            send_request(&conn_config, Method::DELETE, &path, None, "delete snapshot").await?;
+
+        let content = Content::json(DeleteSnapshotResponse { result, time })?;
+
+        Ok(CallToolResult::success(vec![content]))
+    }
+}
+
+async fn get_connection_config() -> Result<ConnectionConfig, McpError> {
+    match CONNECTION_CONFIG.get() {
+        Some(connection_config) => {
+            let conn_config = connection_config.read().await;
+            Ok(conn_config.clone())
+        }
+        None => {
+            let error_message = "Connection config not found";
+            error!("{}", error_message);
+            Err(McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                error_message,
+                None,
+            ))
+        }
+    }
+}
+
+/// Send a request to the Qdrant server, and return the parsed `result` and the `time` of the response
+async fn send_request<T: DeserializeOwned>(
+    conn_config: &ConnectionConfig,
+    method: Method,
+    path: &str,
+    params: Option<Value>,
+    operation: &str,
+) -> Result<(T, f64), McpError> {
+    // build url
+    let base_url = conn_config.base_url.trim_end_matches('/');
+    let url = format!("{base_url}/{path}");
+
+    let mut request = reqwest::Client::new().request(method, &url);
+    if let Some(api_key) = &conn_config.api_key {
+        request = request.header("api-key", api_key);
+    }
+    if let Some(params) = params {
+        request = request
+            .header("Content-Type", "application/json")
+            .json(&params);
+    }
+
+    let response = request.send().await.map_err(|e| {
+        let error_message = format!("Failed to {operation}: {e}");
+        error!("{}", error_message);
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })?;
+
+    let status = response.status();
+    let body = response.bytes().await.map_err(|e| {
+        let error_message = format!("Failed to {operation}: {e}");
+        error!("{}", error_message);
+        McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+    })?;
+
+    match QdrantResponse::<T>::parse(&body) {
+        Ok((result, time)) if status.is_success() => Ok((result, time)),
+        result => {
+            let error_message = match result {
+                Err(QdrantError::Status(error)) => {
+                    format!("Failed to {operation}: {status}. {error}")
+                }
+                Err(e) if status.is_success() => format!("Failed to {operation}. {e}"),
+                _ => format!("Failed to {operation}: {status}"),
+            };
+            error!("{}", error_message);
+            Err(McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                error_message,
+                None,
+            ))
+        }
+    }
+}
+
 #[tool_handler]
 impl ServerHandler for QdrantServer {
     fn get_info(&self) -> ServerInfo {
@@ -194,6 +783,7 @@ impl ServerHandler for QdrantServer {
             capabilities: ServerCapabilities::builder()
                 .enable_tools()
                 .enable_prompts()
+                .enable_resources()
                 .build(),
             server_info: Implementation {
                 name: std::env!("CARGO_PKG_NAME").to_string(),
@@ -247,4 +837,179 @@ impl ServerHandler for QdrantServer {
             }
         }
     }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let (result, _): (CollectionsResult, f64) = send_request(
+            &conn_config,
+            Method::GET,
+            "collections",
+            None,
+            "list collections",
+        )
+        .await?;
+
+        let resources = result
+            .collections
+            .into_iter()
+            .filter(|collection| conn_config.allowed_collections.contains(&collection.name))
+            .map(|collection| {
+                RawResource {
+                    uri: format!("{RESOURCE_URI_PREFIX}{}", collection.name),
+                    description: Some(format!(
+                        "The schema, point count and config of the Qdrant collection `{}`",
+                        collection.name
+                    )),
+                    mime_type: Some("application/json".to_string()),
+                    size: None,
+                    name: collection.name,
+                }
+                .no_annotation()
+            })
+            .collect();
+
+        Ok(ListResourcesResult {
+            next_cursor: None,
+            resources,
+        })
+    }
+
+    async fn list_resource_templates(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ListResourceTemplatesResult, McpError> {
+        Ok(ListResourceTemplatesResult {
+            next_cursor: None,
+            resource_templates: vec![
+                RawResourceTemplate {
+                    uri_template: format!("{RESOURCE_URI_PREFIX}{{name}}"),
+                    name: "collection".to_string(),
+                    description: Some(
+                        "The schema, point count and config of a Qdrant collection".to_string(),
+                    ),
+                    mime_type: Some("application/json".to_string()),
+                }
+                .no_annotation(),
+                RawResourceTemplate {
+                    uri_template: format!("{RESOURCE_URI_PREFIX}{{name}}/points/{{id}}"),
+                    name: "point".to_string(),
+                    description: Some(
+                        "The payload and vector of a point in a Qdrant collection".to_string(),
+                    ),
+                    mime_type: Some("application/json".to_string()),
+                }
+                .no_annotation(),
+            ],
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        ReadResourceRequestParam { uri }: ReadResourceRequestParam,
+        _: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        let conn_config = get_connection_config().await?;
+
+        let resource = parse_resource_uri(&uri).filter(|(collection, _)| {
+            conn_config
+                .allowed_collections
+                .iter()
+                .any(|allowed| allowed == collection)
+        });
+
+        let text = match resource {
+            Some((collection, None)) => {
+                let path = format!("collections/{collection}");
+                let (info, _): (CollectionInfo, f64) =
+                    send_request(&conn_config, Method::GET, &path, None, "get collection").await?;
+
+                serde_json::to_string_pretty(&info)
+            }
+            Some((collection, Some(id))) => {
+                let path = format!("collections/{collection}/points/{id}");
+                let (record, _): (Record, f64) =
+                    send_request(&conn_config, Method::GET, &path, None, "get point").await?;
+
+                serde_json::to_string_pretty(&record)
+            }
+            None => {
+                let error_message = format!("resource not found: {uri}");
+                error!("{}", error_message);
+                return Err(McpError::resource_not_found(error_message, None));
+            }
+        }
+        .map_err(|e| {
+            let error_message = format!("Failed to serialize resource: {e}");
+            error!("{}", error_message);
+            McpError::new(ErrorCode::INTERNAL_ERROR, error_message, None)
+        })?;
+
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri,
+                mime_type: Some("application/json".to_string()),
+                text,
+            }],
+        })
+    }
+}
+
+/// Percent-encode a value used as a single segment of the path of a request, e.g. a
+/// snapshot name, rejecting the values that would still be resolved as `.` or `..`
+fn encode_path_segment(segment: &str, what: &str) -> Result<String, McpError> {
+    if matches!(segment, "" | "." | "..") {
+        let error_message = format!("Invalid {what}: `{segment}`");
+        error!("{}", error_message);
+        return Err(McpError::invalid_params(error_message, None));
+    }
+
+    let mut encoded = String::new();
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    Ok(encoded)
+}
+
+/// Split a resource uri into the collection name and the optional point id, e.g.
+/// `qdrant://collections/docs/points/42` into `("docs", Some(PointId::Num(42)))`
+fn parse_resource_uri(uri: &str) -> Option<(&str, Option<PointId>)> {
+    let path = uri.strip_prefix(RESOURCE_URI_PREFIX)?;
+
+    let (collection, id) = match path.split_once("/points/") {
+        Some((collection, id)) => (collection, Some(parse_point_id(id)?)),
+        None => (path, None),
+    };
+
+    match collection.is_empty() || collection.contains('/') {
+        true => None,
+        false => Some((collection, id)),
+    }
+}
+
+/// Parse a point id, which Qdrant only allows to be an unsigned integer or a UUID
+fn parse_point_id(id: &str) -> Option<PointId> {
+    if id.bytes().all(|byte| byte.is_ascii_digit()) {
+        return id.parse().ok().map(PointId::Num);
+    }
+
+    let is_uuid = match id.len() {
+        32 => id.bytes().all(|byte| byte.is_ascii_hexdigit()),
+        36 => id.bytes().enumerate().all(|(i, byte)| match i {
+            8 | 13 | 18 | 23 => byte == b'-',
+            _ => byte.is_ascii_hexdigit(),
+        }),
+        _ => false,
+    };
+    is_uuid.then(|| PointId::Uuid(id.to_string()))
 }